@@ -0,0 +1,327 @@
+// Conversor de formatos em linha de comando, sem interface gráfica.
+// Usa o mesmo registro de formatos do conversor da interface.
+
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use sliru_tools_lib::enums::format_converter::FormatConverter;
+use sliru_tools_lib::enums::query_language::QueryLanguage;
+use sliru_tools_lib::enums::xml_content_mode::XmlContentMode;
+use sliru_tools_lib::services::format::{self, Format};
+use sliru_tools_lib::services::{csv_converter, xml_converter};
+use sliru_tools_lib::structs::converter_options::ConverterOptions;
+
+const EXIT_CONVERSION: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+const EXIT_IO: u8 = 3;
+
+struct ConvertArgs {
+    from: FormatConverter,
+    to: FormatConverter,
+    input: Option<String>,
+    output: Option<String>,
+    options: ConverterOptions,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("convert") => {}
+        Some("-h" | "--help" | "help") => {
+            print_usage();
+            return ExitCode::SUCCESS;
+        }
+        Some("-V" | "--version") => {
+            println!("sliru {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Some(other) => {
+            eprintln!("error: unknown command '{}'", other);
+            eprintln!("Run 'sliru --help' for usage.");
+            return ExitCode::from(EXIT_USAGE);
+        }
+        None => {
+            print_usage();
+            return ExitCode::from(EXIT_USAGE);
+        }
+    }
+
+    let convert_args = match parse_convert_args(&args[1..]) {
+        Ok(Some(a)) => a,
+        Ok(None) => {
+            print_usage();
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            eprintln!("Run 'sliru --help' for usage.");
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    let input_text = match read_input(convert_args.input.as_deref()) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("error: failed to read input: {}", e);
+            return ExitCode::from(EXIT_IO);
+        }
+    };
+
+    let output_text = match format::convert(
+        &input_text,
+        convert_args.from,
+        convert_args.to,
+        &convert_args.options,
+    ) {
+        Ok(result) => {
+            if let Some(detected) = result.detected {
+                eprintln!(
+                    "detected input format: {} ({:.0}%)",
+                    cli_name(detected.format.format()),
+                    detected.confidence * 100.0
+                );
+            }
+            if let Some(matches) = result.matches {
+                eprintln!("matching nodes: {}", matches);
+            }
+            result.output
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(EXIT_CONVERSION);
+        }
+    };
+
+    if let Err(e) = write_output(convert_args.output.as_deref(), &output_text) {
+        eprintln!("error: failed to write output: {}", e);
+        return ExitCode::from(EXIT_IO);
+    }
+
+    ExitCode::SUCCESS
+}
+
+// Retorna Ok(None) quando a ajuda foi solicitada
+fn parse_convert_args(args: &[String]) -> Result<Option<ConvertArgs>, String> {
+    let mut from = None;
+    let mut to = None;
+    let mut input = None;
+    let mut output = None;
+    let mut options = ConverterOptions::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-f" | "--from" => {
+                let format = parse_format(next_value(&mut iter, arg)?)?;
+                if !format.format().can_parse() {
+                    return Err(format!(
+                        "'{}' can only be used with --to",
+                        cli_name(format.format())
+                    ));
+                }
+                from = Some(format);
+            }
+            "-t" | "--to" => {
+                let format = parse_format(next_value(&mut iter, arg)?)?;
+                if !format.format().can_emit() {
+                    return Err(format!(
+                        "'{}' can only be used with --from",
+                        cli_name(format.format())
+                    ));
+                }
+                to = Some(format);
+            }
+            "-i" | "--input" => input = Some(next_value(&mut iter, arg)?.to_string()),
+            "-o" | "--output" => output = Some(next_value(&mut iter, arg)?.to_string()),
+            "-q" | "--query" => {
+                options.query = Some(next_value(&mut iter, arg)?.to_string());
+                options.query_language = QueryLanguage::JsonPath;
+            }
+            "-x" | "--xpath" => {
+                options.query = Some(next_value(&mut iter, arg)?.to_string());
+                options.query_language = QueryLanguage::XPath;
+            }
+            "--csv-delimiter" => {
+                options.csv.delimiter = csv_char(&mut iter, arg)?.ok_or("CSV delimiter is empty")?
+            }
+            "--csv-quote" => {
+                options.csv.quote = csv_char(&mut iter, arg)?.ok_or("CSV quote is empty")?
+            }
+            "--csv-escape" => options.csv.escape = csv_char(&mut iter, arg)?,
+            "--csv-comment" => options.csv.comment = csv_char(&mut iter, arg)?,
+            "--csv-no-headers" => options.csv.has_headers = false,
+            "--csv-trim" => options.csv.trim = true,
+            "--csv-bom" => options.csv.bom = true,
+            "--csv-flatten" => options.csv.flatten = true,
+            "--csv-infer-types" => options.csv.infer_types = true,
+            "--csv-type" => {
+                let spec = next_value(&mut iter, arg)?;
+                let types = csv_converter::parse_column_types(spec).map_err(|e| e.to_string())?;
+                options.csv.column_types.extend(types);
+            }
+            "--xml-attribute-prefix" => {
+                let prefix = next_value(&mut iter, arg)?;
+                options.xml.attribute_prefix =
+                    xml_converter::parse_attribute_prefix(prefix).map_err(|e| e.to_string())?;
+            }
+            "--xml-keep-root" => options.xml.keep_root = true,
+            "--xml-lossless" => options.xml.content_mode = XmlContentMode::Lossless,
+            "--yaml-multi-document" => options.yaml.multi_document = true,
+            "--yaml-keep-tags" => options.yaml.keep_tags = true,
+            other if other.starts_with('-') && other != "-" => {
+                return Err(format!("unknown option '{}'", other));
+            }
+            // Argumento posicional é tratado como arquivo de entrada
+            other => {
+                if input.is_some() {
+                    return Err(format!("unexpected argument '{}'", other));
+                }
+                input = Some(other.to_string());
+            }
+        }
+    }
+
+    // Com --xpath a saída é a lista de nós, então --to é dispensável
+    let to = match to {
+        Some(to) => to,
+        None if options.query_language == QueryLanguage::XPath => FormatConverter::Xml,
+        None => return Err("missing required option --to".to_string()),
+    };
+
+    Ok(Some(ConvertArgs {
+        from: from.ok_or("missing required option --from")?,
+        to,
+        input,
+        output,
+        options,
+    }))
+}
+
+fn next_value<'a>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+) -> Result<&'a str, String> {
+    iter.next()
+        .map(String::as_str)
+        .ok_or_else(|| format!("option '{}' requires a value", option))
+}
+
+fn csv_char<'a>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+) -> Result<Option<u8>, String> {
+    let value = next_value(iter, option)?;
+    csv_converter::parse_dialect_char(option.trim_start_matches("--csv-"), value)
+        .map_err(|e| e.to_string())
+}
+
+fn parse_format(name: &str) -> Result<FormatConverter, String> {
+    format::registry()
+        .iter()
+        .find(|f| cli_name(**f).eq_ignore_ascii_case(name))
+        .map(|f| f.kind())
+        .ok_or_else(|| {
+            format!(
+                "unknown format '{}' (supported: {})",
+                name,
+                format_names().join(", ")
+            )
+        })
+}
+
+fn format_names() -> Vec<String> {
+    format::registry().iter().map(|f| cli_name(*f)).collect()
+}
+
+// Nome do formato na linha de comando: minúsculo e sem espaços (ex.: json-schema)
+fn cli_name(format: &dyn Format) -> String {
+    format.name().to_lowercase().replace(' ', "-")
+}
+
+// "-" ou ausência de caminho significa stdin/stdout
+fn read_input(path: Option<&str>) -> io::Result<String> {
+    match path {
+        Some(p) if p != "-" => std::fs::read_to_string(p),
+        _ => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf)?;
+            Ok(buf)
+        }
+    }
+}
+
+fn write_output(path: Option<&str>, text: &str) -> io::Result<()> {
+    match path {
+        Some(p) if p != "-" => std::fs::write(p, text),
+        _ => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(text.as_bytes())?;
+            if !text.ends_with('\n') {
+                stdout.write_all(b"\n")?;
+            }
+            stdout.flush()
+        }
+    }
+}
+
+fn print_usage() {
+    println!(
+        "sliru {version}
+
+Usage:
+    sliru convert --from <FORMAT> --to <FORMAT> [--input <FILE>] [--output <FILE>] [OPTIONS]
+    sliru convert --from xml --xpath <EXPR> [--input <FILE>] [--output <FILE>]
+
+Options:
+    -f, --from <FORMAT>    Input format
+    -t, --to <FORMAT>      Output format; json-schema describes the input samples
+                           (items of a top-level array) as a JSON Schema; rust and
+                           typescript generate types for the input
+    -i, --input <FILE>     Input file (default: stdin)
+    -o, --output <FILE>    Output file (default: stdout)
+    -q, --query <JSONPATH> Keep only the matching part of the input, e.g. $.data.items[*].id
+    -x, --xpath <EXPR>     List the XML nodes matching an XPath 1.0 expression,
+                           e.g. //book[@lang='en']/title, instead of converting
+    -h, --help             Show this help
+    -V, --version          Show version
+
+CSV options:
+    --csv-delimiter <CHAR> Field delimiter, e.g. ';', '|' or tab (default: ,)
+    --csv-quote <CHAR>     Quote character (default: double quote)
+    --csv-escape <CHAR>    Escape character for quotes (default: doubled quotes)
+    --csv-comment <CHAR>   Skip input lines starting with this character
+    --csv-no-headers       Input has no header row (columns become col1..colN);
+                           output is written without one
+    --csv-trim             Trim spaces around input fields
+    --csv-bom              Start the output with a UTF-8 BOM
+    --csv-flatten          Write nested values as address.city / tags[0] columns
+                           and rebuild them when reading
+    --csv-infer-types      Read integer, float, boolean and date columns as typed
+                           values when every row agrees (empty cells become null)
+    --csv-type <NAME:TYPE> Force a column type: text, integer, float, boolean or
+                           date (repeatable, or comma-separated)
+
+XML options:
+    --xml-attribute-prefix <PREFIX>
+                           Key prefix for XML attributes (default: @)
+    --xml-keep-root        Keep the XML root element as the top-level key; by
+                           default it is dropped and XML output uses <root>
+    --xml-lossless         Keep mixed content and child order in a $children array
+
+YAML options:
+    --yaml-multi-document  Write a top-level array as a stream of documents (---)
+    --yaml-keep-tags       Keep custom tags (e.g. !Ref) under $tag and $value keys
+
+Formats: {formats}
+
+Exit codes:
+    0  success
+    1  conversion failed (invalid input or unsupported shape)
+    2  invalid command line
+    3  failed to read input or write output",
+        version = env!("CARGO_PKG_VERSION"),
+        formats = format_names().join(", ")
+    );
+}
@@ -0,0 +1,41 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsvColumnType {
+    Text,
+    Integer,
+    Float,
+    Boolean,
+    /// Data ISO 8601 (`2024-05-01`, `2024-05-01T10:00:00Z`). Continua texto no JSON,
+    /// que não tem tipo de data, mas a coluna não é confundida com números.
+    Date,
+}
+
+impl CsvColumnType {
+    /// Tipos testados na inferência, do mais restrito para o mais amplo.
+    pub const INFERRED: [CsvColumnType; 4] = [
+        CsvColumnType::Integer,
+        CsvColumnType::Float,
+        CsvColumnType::Boolean,
+        CsvColumnType::Date,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CsvColumnType::Text => "text",
+            CsvColumnType::Integer => "integer",
+            CsvColumnType::Float => "float",
+            CsvColumnType::Boolean => "boolean",
+            CsvColumnType::Date => "date",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "text" | "string" | "str" => Some(CsvColumnType::Text),
+            "integer" | "int" => Some(CsvColumnType::Integer),
+            "float" | "number" => Some(CsvColumnType::Float),
+            "boolean" | "bool" => Some(CsvColumnType::Boolean),
+            "date" => Some(CsvColumnType::Date),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsvDelimiter {
+    #[default]
+    Comma,
+    /// Padrão do Excel em português.
+    Semicolon,
+    Tab,
+    Pipe,
+}
+
+impl CsvDelimiter {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            CsvDelimiter::Comma => b',',
+            CsvDelimiter::Semicolon => b';',
+            CsvDelimiter::Tab => b'\t',
+            CsvDelimiter::Pipe => b'|',
+        }
+    }
+}
+
+impl From<i32> for CsvDelimiter {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => CsvDelimiter::Semicolon,
+            2 => CsvDelimiter::Tab,
+            3 => CsvDelimiter::Pipe,
+            _ => CsvDelimiter::Comma,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Caminho que só existe no segundo documento.
+    Added,
+    /// Caminho que só existe no primeiro documento.
+    Removed,
+    /// Caminho presente nos dois documentos com valores diferentes.
+    Changed,
+}
+
+impl DiffKind {
+    /// Marcador da linha na listagem de diferenças.
+    pub fn symbol(self) -> char {
+        match self {
+            DiffKind::Added => '+',
+            DiffKind::Removed => '-',
+            DiffKind::Changed => '~',
+        }
+    }
+}
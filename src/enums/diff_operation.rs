@@ -0,0 +1,26 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DiffOperation {
+    /// Lista os caminhos adicionados, removidos e alterados.
+    #[default]
+    Compare,
+    /// JSON Patch (RFC 6902) que transforma o primeiro documento no segundo.
+    GeneratePatch,
+    /// Merge Patch (RFC 7396) que transforma o primeiro documento no segundo.
+    GenerateMergePatch,
+    /// Aplica o JSON Patch do segundo campo ao primeiro documento.
+    ApplyPatch,
+    /// Aplica o Merge Patch do segundo campo ao primeiro documento.
+    ApplyMergePatch,
+}
+
+impl From<i32> for DiffOperation {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => DiffOperation::GeneratePatch,
+            2 => DiffOperation::GenerateMergePatch,
+            3 => DiffOperation::ApplyPatch,
+            4 => DiffOperation::ApplyMergePatch,
+            _ => DiffOperation::Compare,
+        }
+    }
+}
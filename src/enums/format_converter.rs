@@ -1,18 +1,71 @@
+use crate::services::format::{self, Format};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FormatConverter {
     Json,
     Csv,
     Yaml,
     Xml,
+    Toml,
+    Ndjson,
+    Json5,
+    /// Gera um JSON Schema a partir de exemplos; não pode ser usado como entrada.
+    JsonSchema,
+    /// Gera structs Rust (serde) a partir de um exemplo; só de saída.
+    Rust,
+    /// Gera interfaces TypeScript a partir de um exemplo; só de saída.
+    TypeScript,
+    /// Detecta o formato da entrada; não pode ser usado como saída.
+    Auto,
+}
+
+impl FormatConverter {
+    pub fn format(self) -> &'static dyn Format {
+        format::lookup(self)
+    }
+
+    /// Formato no índice `value` das opções de saída.
+    pub fn from_output(value: i32) -> Self {
+        usize::try_from(value)
+            .ok()
+            .and_then(|i| format::output_formats().nth(i))
+            .map(|f| f.kind())
+            .unwrap_or(FormatConverter::Json)
+    }
+
+    /// Formato de entrada de um arquivo pela extensão (sem o ponto).
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        format::input_formats()
+            .find(|f| {
+                f.extensions()
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(extension))
+            })
+            .map(|f| f.kind())
+    }
+
+    /// Índice nas opções de entrada, se o formato puder ser lido.
+    pub fn input_index(self) -> Option<i32> {
+        format::input_formats()
+            .position(|f| f.kind() == self)
+            .map(|i| i as i32)
+    }
+
+    /// Índice nas opções de saída, se o formato puder ser gerado.
+    pub fn output_index(self) -> Option<i32> {
+        format::output_formats()
+            .position(|f| f.kind() == self)
+            .map(|i| i as i32)
+    }
 }
 
+/// Formato no índice `value` das opções de entrada.
 impl From<i32> for FormatConverter {
     fn from(value: i32) -> Self {
-        match value {
-            0 => FormatConverter::Json,
-            1 => FormatConverter::Csv,
-            2 => FormatConverter::Yaml,
-            3 => FormatConverter::Xml,
-            _ => FormatConverter::Json,
-        }
+        usize::try_from(value)
+            .ok()
+            .and_then(|i| format::input_formats().nth(i))
+            .map(|f| f.kind())
+            .unwrap_or(FormatConverter::Json)
     }
 }
@@ -0,0 +1,16 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl From<i32> for HashAlgorithm {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => HashAlgorithm::Md5,
+            1 => HashAlgorithm::Sha1,
+            _ => HashAlgorithm::Sha256,
+        }
+    }
+}
@@ -1 +1,9 @@
+pub mod csv_column_type;
+pub mod csv_delimiter;
+pub mod diff_kind;
+pub mod diff_operation;
 pub mod format_converter;
+pub mod hash_algorithm;
+pub mod query_language;
+pub mod schema_draft;
+pub mod xml_content_mode;
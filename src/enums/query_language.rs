@@ -0,0 +1,17 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueryLanguage {
+    /// Filtra o valor intermediário e converte o resultado para o formato de saída.
+    #[default]
+    JsonPath,
+    /// Consulta a entrada XML e lista os nós encontrados, sem conversão.
+    XPath,
+}
+
+impl From<i32> for QueryLanguage {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => QueryLanguage::XPath,
+            _ => QueryLanguage::JsonPath,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SchemaDraft {
+    /// Draft-07 (também usado para draft-06 e draft-04).
+    Draft7,
+    #[default]
+    Draft202012,
+}
+
+impl SchemaDraft {
+    /// Versão indicada em `$schema`; sem ela, usa a 2020-12.
+    pub fn from_schema(schema: &Value) -> Self {
+        match schema.get("$schema").and_then(Value::as_str) {
+            Some(uri)
+                if ["draft-07", "draft-06", "draft-04"]
+                    .iter()
+                    .any(|d| uri.contains(d)) =>
+            {
+                SchemaDraft::Draft7
+            }
+            _ => SchemaDraft::Draft202012,
+        }
+    }
+}
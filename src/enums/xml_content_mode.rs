@@ -0,0 +1,18 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum XmlContentMode {
+    /// Agrupa filhos por tag e junta os trechos de texto em uma única chave.
+    #[default]
+    Compact,
+    /// Mantém a ordem do documento e os espaços em um array de filhos quando o
+    /// modo compacto os perderia.
+    Lossless,
+}
+
+impl From<i32> for XmlContentMode {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => XmlContentMode::Lossless,
+            _ => XmlContentMode::Compact,
+        }
+    }
+}
@@ -0,0 +1,468 @@
+use crate::enums::csv_delimiter::CsvDelimiter;
+use crate::enums::diff_kind::DiffKind;
+use crate::enums::diff_operation::DiffOperation;
+use crate::enums::format_converter::FormatConverter;
+use crate::enums::hash_algorithm::HashAlgorithm;
+use crate::enums::query_language::QueryLanguage;
+use crate::enums::xml_content_mode::XmlContentMode;
+use crate::services::error::ServiceError;
+use crate::services::{
+    csv_converter, file_hash, format, json_converter, json_diff, json_patch, json_schema,
+    xml_converter,
+};
+use crate::structs::converter_options::ConverterOptions;
+use crate::structs::csv_options::CsvOptions;
+use crate::structs::diff_options::DiffOptions;
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+use rfd::AsyncFileDialog;
+use slint::{ModelRc, SharedString, VecModel};
+
+slint::include_modules!();
+
+// fn abrir_nova_janela() {
+//     let nova_janela = AppWindow::new().unwrap();
+//     // nova_janela.run().unwrap();
+//     nova_janela.show().unwrap();
+// }
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::OnceLock;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[cfg(target_arch = "wasm32")]
+pub fn set_language(language: &str) {
+    match language {
+        "pt_BR" => slint::select_bundled_translation("pt_BR").unwrap(),
+        _ => slint::select_bundled_translation("en").unwrap(), // fallback to English
+    }
+}
+
+#[wasm_bindgen(start)]
+#[cfg(target_arch = "wasm32")]
+pub async fn start_wasm() -> Result<(), JsValue> {
+    Ok(start()?)
+}
+
+// Executor unificado: no desktop usa Tokio multi-thread; no WASM usa o executor do Slint
+#[cfg(not(target_arch = "wasm32"))]
+static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_task<Fut, T>(fut: Fut, on_done: impl FnOnce(T) + Send + 'static)
+where
+    Fut: std::future::Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let rt = RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Erro ao criar runtime tokio")
+    });
+    rt.spawn(async move {
+        let out = fut.await;
+        on_done(out);
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+fn spawn_task<Fut, T>(fut: Fut, on_done: impl FnOnce(T) + 'static)
+where
+    Fut: std::future::Future<Output = T> + 'static,
+{
+    let _ = slint::spawn_local(async move {
+        let out = fut.await;
+        on_done(out);
+    });
+}
+
+fn converter_options(ui: &AppWindow) -> Result<ConverterOptions, ServiceError> {
+    Ok(ConverterOptions {
+        csv: CsvOptions {
+            delimiter: CsvDelimiter::from(ui.get_formatConverterCsvDelimiter()).as_byte(),
+            quote: csv_converter::parse_dialect_char("quote", &ui.get_formatConverterCsvQuote())?
+                .unwrap_or(b'"'),
+            escape: csv_converter::parse_dialect_char("escape", &ui.get_formatConverterCsvEscape())?,
+            comment: csv_converter::parse_dialect_char(
+                "comment",
+                &ui.get_formatConverterCsvComment(),
+            )?,
+            has_headers: ui.get_formatConverterCsvHasHeaders(),
+            trim: ui.get_formatConverterCsvTrim(),
+            bom: ui.get_formatConverterCsvBom(),
+            flatten: ui.get_formatConverterCsvFlatten(),
+            infer_types: ui.get_formatConverterCsvInferTypes(),
+            column_types: csv_converter::parse_column_types(&ui.get_formatConverterCsvColumnTypes())?,
+        },
+        xml: XmlOptions {
+            attribute_prefix: xml_converter::parse_attribute_prefix(
+                &ui.get_formatConverterXmlAttributePrefix(),
+            )?,
+            keep_root: ui.get_formatConverterXmlKeepRoot(),
+            content_mode: XmlContentMode::from(ui.get_formatConverterXmlContentMode()),
+            ..XmlOptions::default()
+        },
+        yaml: YamlOptions {
+            multi_document: ui.get_formatConverterYamlMultiDocument(),
+            keep_tags: ui.get_formatConverterYamlKeepTags(),
+            ..YamlOptions::default()
+        },
+        query: Some(ui.get_formatConverterQuery().to_string()),
+        query_language: QueryLanguage::from(ui.get_formatConverterQueryLanguage()),
+    })
+}
+
+// Destaca do ponto do erro até o fim da linha; no fim da linha usa o caractere anterior
+fn error_highlight_range(text: &str, offset: usize) -> (usize, usize) {
+    let end = text[offset..]
+        .find('\n')
+        .map(|i| offset + i)
+        .unwrap_or(text.len());
+    if end > offset {
+        return (offset, end);
+    }
+    let start = text[..offset]
+        .char_indices()
+        .next_back()
+        .map(|(i, _)| i)
+        .unwrap_or(offset);
+    (start, offset)
+}
+
+pub fn start() -> Result<(), slint::PlatformError> {
+    let ui = AppWindow::new()?;
+
+    let format_names: Vec<SharedString> = format::input_formats()
+        .map(|f| SharedString::from(f.name()))
+        .collect();
+    let output_format_names: Vec<SharedString> = format::output_formats()
+        .map(|f| SharedString::from(f.name()))
+        .collect();
+    ui.set_formatConverterFormats(ModelRc::new(VecModel::from(format_names)));
+    ui.set_formatConverterOutputFormats(ModelRc::new(VecModel::from(output_format_names)));
+
+    ui.on_change_language({
+        move |language| match language.as_str() {
+            "English" => {
+                slint::select_bundled_translation("en").unwrap();
+            }
+            "Português Brasileiro" => {
+                slint::select_bundled_translation("pt_BR").unwrap();
+            }
+            _ => {}
+        }
+    });
+
+    ui.on_hdoc_request_execute({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.upgrade().unwrap();
+            let input_text = ui.get_hdocRequestInputText();
+
+            match crate::services::hdoc_request::parse_heredoc_request(&input_text) {
+                Ok(request_data) => {
+                    let ui_weak = ui.as_weak();
+                    spawn_task(
+                        async move { crate::services::hdoc_request::send_request(&request_data).await },
+                        move |result| {
+                            let _ = slint::invoke_from_event_loop(move || {
+                                if let Some(ui) = ui_weak.upgrade() {
+                                    match result {
+                                        Ok(result) => {
+                                            let mut output = format!(
+                                                "Status Code: {}\n",
+                                                result.status_code
+                                            );
+                                            output.push_str("Headers:\n");
+                                            for (k, v) in result.headers {
+                                                output.push_str(&format!("{}: {}\n", k, v));
+                                            }
+                                            output.push_str("\nBody:\n");
+                                            output.push_str(&result.body);
+                                            ui.set_hdocRequestOutputText(SharedString::from(output));
+                                        }
+                                        Err(e) => {
+                                            ui.set_hdocRequestOutputText(SharedString::from(format!(
+                                                "Error executing request: {}",
+                                                e
+                                            )));
+                                        }
+                                    }
+                                }
+                            });
+                        },
+                    );
+                }
+                Err(e) => {
+                    ui.set_hdocRequestOutputText(SharedString::from(format!(
+                        "Error parsing request: {}",
+                        e
+                    )));
+                }
+            }
+        }
+    });
+
+    ui.on_open_file_verify({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            let _ = slint::spawn_local(async move {
+                if let Some(handle) = AsyncFileDialog::new()
+                    .add_filter("*", &["*"])
+                    .pick_file()
+                    .await
+                {
+                    let data = handle.read().await;
+
+                    let algorithm = HashAlgorithm::from(ui.get_fileVerifyOutputFormat());
+                    let hex = file_hash::hash_hex(&data, algorithm);
+                    ui.set_fileVerifyOutputText(hex.into());
+                }
+            });
+        }
+    });
+
+    ui.on_format_converter_inverter({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            let input_format = FormatConverter::from(ui.get_formatConverterInputFormat());
+            let output_format = FormatConverter::from_output(ui.get_formatConverterOutputFormat());
+            // Auto não existe na saída: usa o formato detectado, se houver
+            let new_output_format = if input_format.format().can_emit() {
+                Some(input_format)
+            } else {
+                let detected = ui.get_formatConverterDetectedFormat();
+                format::registry()
+                    .iter()
+                    .find(|f| f.name() == detected.as_str())
+                    .map(|f| f.kind())
+            };
+            // Formatos só de saída (ex.: JSON Schema) não podem virar entrada
+            let new_input_index = output_format.input_index();
+            let new_output_index = new_output_format.and_then(|f| f.output_index());
+            if let (Some(new_input_index), Some(new_output_index)) =
+                (new_input_index, new_output_index)
+            {
+                ui.set_formatConverterInputFormat(new_input_index);
+                ui.set_formatConverterOutputFormat(new_output_index);
+            }
+        }
+    });
+
+    ui.on_format_converter_open({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            let _ = slint::spawn_local(async move {
+                if let Some(handle) = AsyncFileDialog::new()
+                    .add_filter("*", &["*"])
+                    .pick_file()
+                    .await
+                {
+                    let data = handle.read().await;
+                    let Ok(text) = String::from_utf8(data) else {
+                        ui.set_formatConverterOutputText(
+                            format!("Error opening file: {} is not UTF-8 text", handle.file_name())
+                                .into(),
+                        );
+                        return;
+                    };
+                    // Extensão desconhecida (ou sem extensão) usa a detecção automática
+                    let format = handle
+                        .file_name()
+                        .rsplit_once('.')
+                        .and_then(|(_, extension)| FormatConverter::from_extension(extension))
+                        .unwrap_or(FormatConverter::Auto);
+                    if let Some(index) = format.input_index() {
+                        ui.set_formatConverterInputFormat(index);
+                    }
+                    ui.set_formatConverterInputText(text.into());
+                }
+            });
+        }
+    });
+
+    ui.on_format_converter_save({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            let format = FormatConverter::from_output(ui.get_formatConverterOutputFormat());
+            let extension = format.format().extensions().first().copied().unwrap_or("txt");
+            let text = ui.get_formatConverterOutputText();
+            // No WASM, `save_file` não abre diálogo e `write` dispara o download no navegador
+            let _ = slint::spawn_local(async move {
+                if let Some(handle) = AsyncFileDialog::new()
+                    .add_filter(format.format().name(), &[extension])
+                    .set_file_name(format!("output.{}", extension))
+                    .save_file()
+                    .await
+                    && let Err(e) = handle.write(text.as_bytes()).await
+                {
+                    ui.set_formatConverterOutputText(format!("Error saving file: {}", e).into());
+                }
+            });
+        }
+    });
+
+    ui.on_format_converter_execute({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            let input_format = FormatConverter::from(ui.get_formatConverterInputFormat());
+            let output_format = FormatConverter::from_output(ui.get_formatConverterOutputFormat());
+            let input_text = ui.get_formatConverterInputText();
+            let result = converter_options(&ui).and_then(|options| {
+                format::convert(&input_text, input_format, output_format, &options)
+            });
+
+            match result {
+                Ok(result) => {
+                    ui.set_formatConverterOutputText(result.output.into());
+                    ui.set_formatConverterDocumentCount(result.documents as i32);
+                    ui.set_formatConverterMatchCount(result.matches.map_or(-1, |m| m as i32));
+                    match result.detected {
+                        Some(detected) => {
+                            ui.set_formatConverterDetectedFormat(detected.format.format().name().into());
+                            ui.set_formatConverterDetectedConfidence(
+                                (detected.confidence * 100.0).round() as i32,
+                            );
+                        }
+                        None => ui.set_formatConverterDetectedFormat(SharedString::new()),
+                    }
+                }
+                Err(e) => {
+                    ui.set_formatConverterOutputText(e.to_string().into());
+                    ui.set_formatConverterDocumentCount(0);
+                    ui.set_formatConverterMatchCount(-1);
+                    ui.set_formatConverterDetectedFormat(SharedString::new());
+                    if let Some(location) = e.location() {
+                        let (start, end) = error_highlight_range(&input_text, location.offset);
+                        ui.set_formatConverterErrorStart(start as i32);
+                        ui.set_formatConverterErrorEnd(end as i32);
+                        // Incrementa para o container selecionar mesmo se a posição se repetir
+                        ui.set_formatConverterErrorRevision(
+                            ui.get_formatConverterErrorRevision().wrapping_add(1),
+                        );
+                    }
+                }
+            }
+        }
+    });
+
+    ui.on_diff_execute({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            let options = ConverterOptions::default();
+            let diff_options = DiffOptions {
+                ignore_array_order: ui.get_diffIgnoreArrayOrder(),
+            };
+            let left_format = FormatConverter::from(ui.get_diffLeftFormat());
+            let left = format::parse_input(&ui.get_diffLeftText(), left_format, &options);
+            let right = format::parse_input(
+                &ui.get_diffRightText(),
+                FormatConverter::from(ui.get_diffRightFormat()),
+                &options,
+            );
+
+            // O documento com o patch aplicado sai no formato do primeiro campo
+            let output_format = if left_format.format().can_emit() {
+                left_format
+            } else {
+                FormatConverter::Json
+            };
+
+            match (left, right) {
+                (Ok(left), Ok(right)) => {
+                    ui.set_diffErrorSide(0);
+                    let output = match DiffOperation::from(ui.get_diffOperation()) {
+                        DiffOperation::Compare => {
+                            let entries = json_diff::diff(&left, &right, &diff_options);
+                            let count =
+                                |kind| entries.iter().filter(|e| e.kind == kind).count() as i32;
+                            ui.set_diffAdded(count(DiffKind::Added));
+                            ui.set_diffRemoved(count(DiffKind::Removed));
+                            ui.set_diffChanged(count(DiffKind::Changed));
+                            Ok(json_diff::format_diff(&entries))
+                        }
+                        DiffOperation::GeneratePatch => {
+                            json_converter::value_to_json(&json_patch::generate_patch(&left, &right))
+                        }
+                        DiffOperation::GenerateMergePatch => json_converter::value_to_json(
+                            &json_patch::generate_merge_patch(&left, &right),
+                        ),
+                        DiffOperation::ApplyPatch => json_patch::apply_patch(&left, &right)
+                            .and_then(|v| output_format.format().emit(&v, &options)),
+                        DiffOperation::ApplyMergePatch => output_format
+                            .format()
+                            .emit(&json_patch::apply_merge_patch(&left, &right), &options),
+                    };
+                    match output {
+                        Ok(text) => ui.set_diffOutputText(text.into()),
+                        Err(e) => ui.set_diffOutputText(e.to_string().into()),
+                    }
+                }
+                (Err(e), _) => {
+                    ui.set_diffErrorSide(1);
+                    ui.set_diffOutputText(e.to_string().into());
+                }
+                (_, Err(e)) => {
+                    ui.set_diffErrorSide(2);
+                    ui.set_diffOutputText(e.to_string().into());
+                }
+            }
+        }
+    });
+
+    ui.on_schema_validate({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            let options = ConverterOptions::default();
+            let schema = format::parse_input(
+                &ui.get_schemaValidatorSchemaText(),
+                FormatConverter::from(ui.get_schemaValidatorSchemaFormat()),
+                &options,
+            );
+            let instance = format::parse_input(
+                &ui.get_schemaValidatorInstanceText(),
+                FormatConverter::from(ui.get_schemaValidatorInstanceFormat()),
+                &options,
+            );
+
+            // Um schema que não pode ser usado (ex.: `$ref` sem destino) conta
+            // como erro do schema, não como violação do documento
+            let result = match (schema, instance) {
+                (Ok(schema), Ok(instance)) => {
+                    json_schema::validate(&schema, &instance).map_err(|e| (1, e))
+                }
+                (Err(e), _) => Err((1, e)),
+                (_, Err(e)) => Err((2, e)),
+            };
+            match result {
+                Ok(violations) => {
+                    ui.set_schemaValidatorErrorSide(0);
+                    ui.set_schemaValidatorViolations(violations.len() as i32);
+                    ui.set_schemaValidatorOutputText(
+                        json_schema::format_violations(&violations).into(),
+                    );
+                }
+                Err((side, e)) => {
+                    ui.set_schemaValidatorErrorSide(side);
+                    ui.set_schemaValidatorViolations(-1);
+                    ui.set_schemaValidatorOutputText(e.to_string().into());
+                }
+            }
+        }
+    });
+
+    ui.run()?;
+
+    Ok(())
+}
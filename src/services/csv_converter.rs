@@ -1,45 +1,548 @@
-use csv::ReaderBuilder;
-use serde::Serialize;
+use std::collections::{BTreeSet, HashSet};
+
+use csv::{ReaderBuilder, StringRecord, Trim, WriterBuilder};
 use serde_json::{Map, Value};
 
-use crate::services::json_converter;
+use crate::enums::csv_column_type::CsvColumnType;
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::{ServiceError, SourceLocation};
+use crate::services::format::Format;
+use crate::services::{
+    json_converter, ndjson_converter, toml_converter, xml_converter, yaml_converter,
+};
+use crate::structs::converter_options::ConverterOptions;
+use crate::structs::csv_options::CsvOptions;
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+
+pub struct CsvFormat;
+
+impl Format for CsvFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::Csv
+    }
+
+    fn name(&self) -> &'static str {
+        "CSV"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["csv"]
+    }
+
+    fn parse(&self, input: &str, options: &ConverterOptions) -> Result<Value, ServiceError> {
+        csv_to_value(input, &options.csv)
+    }
+
+    fn emit(&self, value: &Value, options: &ConverterOptions) -> Result<String, ServiceError> {
+        value_to_csv(value, &options.csv)
+    }
+}
 
-pub fn csv_to_json(csv_str: &str) -> Result<String, String> {
+pub fn csv_to_value(csv_str: &str, options: &CsvOptions) -> Result<Value, ServiceError> {
     let mut rdr = ReaderBuilder::new()
         .flexible(true)
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .escape(options.escape)
+        .double_quote(options.escape.is_none())
+        .has_headers(options.has_headers)
+        .trim(if options.trim { Trim::All } else { Trim::None })
+        .comment(options.comment)
         .from_reader(csv_str.as_bytes());
 
-    let headers = rdr
-        .headers()
-        .map_err(|e| format!("Erro ao ler cabeçalhos do CSV: {}", e))?
-        .clone();
+    let headers = if options.has_headers {
+        rdr.headers()
+            .map_err(|e| ServiceError::parse("CSV", e))?
+            .clone()
+    } else {
+        StringRecord::new()
+    };
+
+    let records = rdr
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ServiceError::parse("CSV", e))?;
+    // Sem cabeçalho, as colunas são numeradas até a linha mais longa
+    let headers = if options.has_headers {
+        headers
+    } else {
+        let columns = records.iter().map(StringRecord::len).max().unwrap_or(0);
+        (1..=columns).map(|i| format!("col{}", i)).collect()
+    };
+
+    // Caminhos aninhados de cada cabeçalho, calculados uma vez só
+    let paths: Vec<Vec<PathSegment>> = if options.flatten {
+        headers
+            .iter()
+            .map(|h| parse_path(h, headers.len()))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let types = column_types(&headers, &records, options)?;
 
     let mut out = Vec::new();
-    for result in rdr.records() {
-        let record = result.map_err(|e| format!("Erro ao ler linha do CSV: {}", e))?;
+    for record in &records {
         let mut map = Map::new();
-        for (header, field) in headers.iter().zip(record.iter()) {
-            map.insert(header.to_string(), Value::String(field.to_string()));
+        for ((header, field), column_type) in headers.iter().zip(record.iter()).zip(&types) {
+            let value = match column_type {
+                None => Value::String(field.to_string()),
+                Some(t) => typed_cell(field, *t).ok_or_else(|| {
+                    let offset = record.position().map_or(0, |p| p.byte() as usize);
+                    ServiceError::parse_at(
+                        "CSV",
+                        format!(
+                            "column '{}' expects {}, found '{}'",
+                            header,
+                            t.name(),
+                            field
+                        ),
+                        SourceLocation::from_offset(csv_str, offset),
+                    )
+                })?,
+            };
+            map.insert(header.to_string(), value);
+        }
+        if options.flatten {
+            out.push(unflatten_row(map, &paths));
+        } else {
+            out.push(Value::Object(map));
+        }
+    }
+    Ok(Value::Array(out))
+}
+
+/// Lê um caractere do dialeto (`;`, `tab`, `|`...). Texto vazio significa nenhum.
+pub fn parse_dialect_char(option: &str, value: &str) -> Result<Option<u8>, ServiceError> {
+    match value {
+        "" => Ok(None),
+        "tab" | "\\t" | "\t" => Ok(Some(b'\t')),
+        _ if value.len() == 1 && value.is_ascii() => Ok(Some(value.as_bytes()[0])),
+        _ => Err(ServiceError::validation(format!(
+            "CSV {} must be a single ASCII character, got '{}'",
+            option, value
+        ))),
+    }
+}
+
+/// Lê `idade:integer, ativo:boolean` como tipos forçados de colunas.
+pub fn parse_column_types(spec: &str) -> Result<Vec<(String, CsvColumnType)>, ServiceError> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            item.rsplit_once(':')
+                .and_then(|(name, t)| Some((name.trim().to_string(), CsvColumnType::from_name(t)?)))
+                .ok_or_else(|| {
+                    ServiceError::validation(format!(
+                        "invalid column type '{}' (expected name:text|integer|float|boolean|date)",
+                        item
+                    ))
+                })
+        })
+        .collect()
+}
+
+// None mantém a coluna como texto puro; tipos inferidos exigem que todas as células concordem
+fn column_types(
+    headers: &StringRecord,
+    records: &[StringRecord],
+    options: &CsvOptions,
+) -> Result<Vec<Option<CsvColumnType>>, ServiceError> {
+    // Um tipo forçado para coluna que não existe é quase sempre erro de digitação
+    if let Some((name, _)) = options
+        .column_types
+        .iter()
+        .find(|(name, _)| !headers.iter().any(|h| h == name))
+    {
+        return Err(ServiceError::validation(format!(
+            "unknown CSV column '{}' in column types",
+            name
+        )));
+    }
+    Ok(headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            if let Some((_, t)) = options.column_types.iter().find(|(name, _)| name == header) {
+                return Some(*t);
+            }
+            if !options.infer_types {
+                return None;
+            }
+            let cells: Vec<&str> = records.iter().filter_map(|r| r.get(i)).collect();
+            let inferred = CsvColumnType::INFERRED.into_iter().find(|t| {
+                cells.iter().any(|c| !c.is_empty())
+                    && cells.iter().all(|c| typed_cell(c, *t).is_some())
+            });
+            Some(inferred.unwrap_or(CsvColumnType::Text))
+        })
+        .collect())
+}
+
+// Retorna None quando a célula não pode ser lida como o tipo da coluna
+fn typed_cell(field: &str, column_type: CsvColumnType) -> Option<Value> {
+    if field.is_empty() {
+        return Some(Value::Null);
+    }
+    match column_type {
+        CsvColumnType::Text => Some(Value::String(field.to_string())),
+        CsvColumnType::Integer => {
+            if has_leading_zero(field) {
+                return None;
+            }
+            field.parse::<i64>().ok().map(Value::from)
+        }
+        CsvColumnType::Float => {
+            // Recusa `inf`, `NaN` e `+1`, que o parse de f64 aceita
+            if has_leading_zero(field)
+                || !field.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.')
+            {
+                return None;
+            }
+            let f = field.parse::<f64>().ok()?;
+            serde_json::Number::from_f64(f).map(Value::Number)
+        }
+        CsvColumnType::Boolean => {
+            if field.eq_ignore_ascii_case("true") {
+                Some(Value::Bool(true))
+            } else if field.eq_ignore_ascii_case("false") {
+                Some(Value::Bool(false))
+            } else {
+                None
+            }
+        }
+        CsvColumnType::Date => is_iso_date(field).then(|| Value::String(field.to_string())),
+    }
+}
+
+// Zeros à esquerda (CEP, códigos) não são números
+fn has_leading_zero(field: &str) -> bool {
+    let digits = field.strip_prefix('-').unwrap_or(field).as_bytes();
+    digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit()
+}
+
+// AAAA-MM-DD, opcionalmente seguido de hora (`T` ou espaço) e fuso
+fn is_iso_date(field: &str) -> bool {
+    let bytes = field.as_bytes();
+    let digits = |range: std::ops::Range<usize>| {
+        bytes
+            .get(range.clone())
+            .is_some_and(|b| b.iter().all(u8::is_ascii_digit))
+            .then(|| field[range].parse::<u32>().unwrap_or(0))
+    };
+    let (Some(_), Some(month), Some(day)) = (digits(0..4), digits(5..7), digits(8..10)) else {
+        return false;
+    };
+    if bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+    {
+        return false;
+    }
+    let time = &field[10..];
+    if time.is_empty() {
+        return true;
+    }
+    let Some(time) = time.strip_prefix(['T', ' ']) else {
+        return false;
+    };
+    let tb = time.as_bytes();
+    let (Some(hour), Some(minute)) = (
+        time.get(0..2).and_then(|h| h.parse::<u32>().ok()),
+        time.get(3..5).and_then(|m| m.parse::<u32>().ok()),
+    ) else {
+        return false;
+    };
+    if tb[2] != b':' || hour > 23 || minute > 59 {
+        return false;
+    }
+    // Segundos, frações e fuso ficam no resto: só dígitos e separadores
+    time[5..]
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b':' | b'.' | b'+' | b'-' | b'Z'))
+}
+
+pub fn value_to_csv(v: &Value, options: &CsvOptions) -> Result<String, ServiceError> {
+    let flattened;
+    let v = if options.flatten {
+        flattened = flatten_rows(v);
+        &flattened
+    } else {
+        v
+    };
+    let mut wtr = WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .escape(options.escape.unwrap_or(b'\\'))
+        .double_quote(options.escape.is_none())
+        .from_writer(vec![]);
+
+    match v {
+        Value::Array(arr) => {
+            let headers_vec = collect_headers(arr, options);
+            if options.has_headers {
+                wtr.write_record(&headers_vec)
+                    .map_err(|e| ServiceError::serialize("CSV", e))?;
+            }
+
+            for item in arr {
+                if let Value::Object(map) = item {
+                    let row: Vec<String> = headers_vec
+                        .iter()
+                        .map(|h| map.get(h).map(value_to_cell).unwrap_or_default())
+                        .collect();
+                    wtr.write_record(&row)
+                        .map_err(|e| ServiceError::serialize("CSV", e))?;
+                } else {
+                    wtr.write_record(&[item.to_string()])
+                        .map_err(|e| ServiceError::serialize("CSV", e))?;
+                }
+            }
+        }
+        Value::Object(map) => {
+            let headers_vec: Vec<String> = map.keys().cloned().collect();
+            if options.has_headers {
+                wtr.write_record(&headers_vec)
+                    .map_err(|e| ServiceError::serialize("CSV", e))?;
+            }
+            let row: Vec<String> = headers_vec
+                .iter()
+                .map(|h| map.get(h).map(value_to_cell).unwrap_or_default())
+                .collect();
+            wtr.write_record(&row)
+                .map_err(|e| ServiceError::serialize("CSV", e))?;
+        }
+        _ => {
+            if options.has_headers {
+                wtr.write_record(["value"])
+                    .map_err(|e| ServiceError::serialize("CSV", e))?;
+            }
+            wtr.write_record(&[v.to_string()])
+                .map_err(|e| ServiceError::serialize("CSV", e))?;
+        }
+    }
+
+    wtr.flush().map_err(|e| ServiceError::serialize("CSV", e))?;
+    let data = wtr
+        .into_inner()
+        .map_err(|e| ServiceError::serialize("CSV", e))?;
+    let csv_string = String::from_utf8(data).map_err(ServiceError::encoding)?;
+    if options.bom {
+        return Ok(format!("\u{feff}{}", csv_string));
+    }
+    Ok(csv_string)
+}
+
+// Colunas achatadas seguem a ordem em que aparecem, para manter `tags[2]` antes de `tags[10]`
+fn collect_headers(arr: &[Value], options: &CsvOptions) -> Vec<String> {
+    let maps = arr.iter().filter_map(Value::as_object);
+    if options.flatten {
+        let mut seen = HashSet::new();
+        maps.flat_map(|map| map.keys())
+            .filter(|k| seen.insert(k.as_str()))
+            .cloned()
+            .collect()
+    } else {
+        let headers: BTreeSet<&String> = maps.flat_map(|map| map.keys()).collect();
+        headers.into_iter().cloned().collect()
+    }
+}
+
+fn flatten_rows(v: &Value) -> Value {
+    let flatten_item = |item: &Value| match item {
+        Value::Object(_) => {
+            let mut out = Map::new();
+            flatten_value("", item, &mut out);
+            Value::Object(out)
+        }
+        _ => item.clone(),
+    };
+    match v {
+        Value::Array(arr) => Value::Array(arr.iter().map(flatten_item).collect()),
+        _ => flatten_item(v),
+    }
+}
+
+// Objetos e arrays vazios ficam como JSON na célula, já que não geram colunas
+fn flatten_value(prefix: &str, value: &Value, out: &mut Map<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                if prefix.is_empty() {
+                    flatten_value(k, v, out);
+                } else {
+                    flatten_value(&format!("{}.{}", prefix, k), v, out);
+                }
+            }
+        }
+        Value::Array(arr) if !arr.is_empty() => {
+            for (i, v) in arr.iter().enumerate() {
+                flatten_value(&format!("{}[{}]", prefix, i), v, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+// `address.city` vira [Key, Key] e `tags[0]` vira [Key, Index]; cabeçalhos que
+// não seguem esse formato ficam como uma chave só. Um índice a partir de
+// `columns` não sai do achatamento (cada item ocupa uma coluna) e também fica
+// literal, em vez de alocar um array enorme
+fn parse_path(header: &str, columns: usize) -> Vec<PathSegment> {
+    let literal = || vec![PathSegment::Key(header.to_string())];
+    let mut path = Vec::new();
+    for part in header.split('.') {
+        let (key, mut indexes) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if key.is_empty() && (path.is_empty() || indexes.is_empty()) {
+            return literal();
+        }
+        if !key.is_empty() {
+            path.push(PathSegment::Key(key.to_string()));
+        }
+        while !indexes.is_empty() {
+            let Some((index, rest)) = indexes.strip_prefix('[').and_then(|s| s.split_once(']'))
+            else {
+                return literal();
+            };
+            let Some(index) = index.parse().ok().filter(|i| *i < columns) else {
+                return literal();
+            };
+            path.push(PathSegment::Index(index));
+            indexes = rest;
+        }
+    }
+    path
+}
+
+fn unflatten_row(map: Map<String, Value>, paths: &[Vec<PathSegment>]) -> Value {
+    let mut row = Value::Object(Map::new());
+    for ((header, value), path) in map.into_iter().zip(paths) {
+        // Células vazias em colunas aninhadas são itens que a linha não tem
+        if path.len() > 1 && (value.is_null() || value.as_str() == Some("")) {
+            continue;
+        }
+        if !insert_path(&mut row, path, value.clone())
+            && let Value::Object(obj) = &mut row
+        {
+            // Conflito (ex.: `a` e `a.b`): mantém a coluna como chave literal
+            obj.insert(header, value);
+        }
+    }
+    row
+}
+
+fn insert_path(slot: &mut Value, path: &[PathSegment], value: Value) -> bool {
+    let Some((first, rest)) = path.split_first() else {
+        if slot.is_null() {
+            *slot = value;
+            return true;
         }
-        out.push(Value::Object(map));
+        return false;
+    };
+    match first {
+        PathSegment::Key(key) => {
+            if slot.is_null() {
+                *slot = Value::Object(Map::new());
+            }
+            let Value::Object(map) = slot else {
+                return false;
+            };
+            insert_path(map.entry(key.clone()).or_insert(Value::Null), rest, value)
+        }
+        PathSegment::Index(index) => {
+            if slot.is_null() {
+                *slot = Value::Array(Vec::new());
+            }
+            let Value::Array(arr) = slot else {
+                return false;
+            };
+            if arr.len() <= *index {
+                arr.resize(index + 1, Value::Null);
+            }
+            insert_path(&mut arr[*index], rest, value)
+        }
+    }
+}
+
+fn value_to_cell(v: &Value) -> String {
+    match v {
+        Value::Null => "".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(_) | Value::Object(_) => serde_json::to_string(v).unwrap_or_default(),
     }
+}
 
-    let mut buf = Vec::new();
-    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
-    let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
-    out.serialize(&mut ser)
-        .map_err(|e| format!("Erro ao serializar JSON: {}", e))?;
-    String::from_utf8(buf).map_err(|e| format!("Erro ao converter JSON para UTF-8: {}", e))
+pub fn csv_to_json(csv_str: &str) -> Result<String, ServiceError> {
+    csv_to_value(csv_str, &CsvOptions::default()).and_then(|v| json_converter::value_to_json(&v))
 }
 
-pub fn csv_to_yaml(csv_str: &str) -> Result<String, String> {
-    csv_to_json(csv_str).and_then(|json_str| json_converter::json_to_yaml(&json_str))
+pub fn csv_to_yaml(csv_str: &str) -> Result<String, ServiceError> {
+    csv_to_value(csv_str, &CsvOptions::default())
+        .and_then(|v| yaml_converter::value_to_yaml(&v, &YamlOptions::default()))
 }
 
-pub fn pretty_csv(csv_str: &str) -> Result<String, String> {
-    csv_to_json(csv_str).and_then(|json_str| json_converter::json_to_csv(&json_str))
+pub fn pretty_csv(csv_str: &str) -> Result<String, ServiceError> {
+    csv_to_value(csv_str, &CsvOptions::default())
+        .and_then(|v| value_to_csv(&v, &CsvOptions::default()))
 }
 
-pub fn csv_to_xml(csv_str: &str) -> Result<String, String> {
-    csv_to_json(csv_str).and_then(|json_str| json_converter::json_to_xml(&json_str))
+pub fn csv_to_xml(csv_str: &str) -> Result<String, ServiceError> {
+    csv_to_value(csv_str, &CsvOptions::default())
+        .and_then(|v| xml_converter::value_to_xml(&v, &XmlOptions::default()))
+}
+
+pub fn csv_to_toml(csv_str: &str) -> Result<String, ServiceError> {
+    csv_to_value(csv_str, &CsvOptions::default()).and_then(|v| toml_converter::value_to_toml(&v))
+}
+
+pub fn csv_to_ndjson(csv_str: &str) -> Result<String, ServiceError> {
+    csv_to_value(csv_str, &CsvOptions::default())
+        .and_then(|v| ndjson_converter::value_to_ndjson(&v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn huge_flattened_index_stays_literal() {
+        let options = CsvOptions {
+            flatten: true,
+            ..CsvOptions::default()
+        };
+        let value = csv_to_value("id,tags[99999999999]\n1,a\n", &options).unwrap();
+        assert_eq!(value[0]["tags[99999999999]"], "a");
+    }
+
+    #[test]
+    fn unknown_forced_column_is_an_error() {
+        let options = CsvOptions {
+            column_types: vec![("missing".to_string(), CsvColumnType::Integer)],
+            ..CsvOptions::default()
+        };
+        let error = csv_to_value("id\n1\n", &options).unwrap_err();
+        assert!(error.to_string().contains("'missing'"));
+    }
+
+    #[test]
+    fn flattened_index_builds_array() {
+        let options = CsvOptions {
+            flatten: true,
+            ..CsvOptions::default()
+        };
+        let value = csv_to_value("tags[0],tags[1]\na,b\n", &options).unwrap();
+        assert_eq!(value[0]["tags"], serde_json::json!(["a", "b"]));
+    }
 }
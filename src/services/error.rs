@@ -0,0 +1,223 @@
+use std::error::Error;
+use std::fmt;
+
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Posição de um erro no texto de entrada.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Linha, começando em 1.
+    pub line: usize,
+    /// Coluna em caracteres, começando em 1.
+    pub column: usize,
+    /// Posição em bytes (UTF-8) desde o início do texto.
+    pub offset: usize,
+}
+
+impl SourceLocation {
+    pub fn from_offset(text: &str, offset: usize) -> Self {
+        let mut offset = offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        SourceLocation {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            offset,
+        }
+    }
+
+    /// `column` em caracteres, começando em 0 (como no `Marker` do yaml-rust2).
+    pub(crate) fn from_line_char_column(text: &str, line: usize, column: usize) -> Self {
+        let start = line_start(text, line);
+        let width: usize = text[start..]
+            .chars()
+            .take_while(|c| *c != '\n')
+            .take(column)
+            .map(char::len_utf8)
+            .sum();
+        Self::from_offset(text, start + width)
+    }
+
+    /// `column` em bytes, começando em 1 (como no `serde_json::Error`).
+    pub(crate) fn from_line_byte_column(text: &str, line: usize, column: usize) -> Self {
+        Self::from_offset(text, line_start(text, line) + column.saturating_sub(1))
+    }
+}
+
+fn line_start(text: &str, line: usize) -> usize {
+    if line <= 1 {
+        return 0;
+    }
+    text.match_indices('\n')
+        .nth(line - 2)
+        .map(|(i, _)| i + 1)
+        .unwrap_or(text.len())
+}
+
+/// Mensagem do parser sem a posição, que fica em `SourceLocation`.
+#[derive(Debug)]
+struct SyntaxError {
+    message: String,
+    source: BoxError,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for SyntaxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Erro retornado pelas funções públicas de `services`.
+#[derive(Debug)]
+pub enum ServiceError {
+    /// A entrada não é um documento válido no formato informado.
+    Parse {
+        format: &'static str,
+        source: BoxError,
+        location: Option<SourceLocation>,
+    },
+    /// Falha ao gerar a saída no formato informado.
+    Serialize {
+        format: &'static str,
+        source: BoxError,
+    },
+    /// Texto com codificação inválida (ex.: bytes que não são UTF-8).
+    Encoding { source: BoxError },
+    /// Documento válido, mas com uma estrutura que o formato de destino não representa.
+    UnsupportedShape {
+        format: &'static str,
+        message: String,
+    },
+    /// Falha ao montar, enviar ou ler uma requisição HTTP.
+    Http { source: reqwest::Error },
+    /// Dados de entrada que não passam nas regras da ferramenta.
+    Validation { message: String },
+}
+
+impl ServiceError {
+    pub fn parse(format: &'static str, source: impl Into<BoxError>) -> Self {
+        ServiceError::Parse {
+            format,
+            source: source.into(),
+            location: None,
+        }
+    }
+
+    pub fn parse_at(
+        format: &'static str,
+        source: impl Into<BoxError>,
+        location: SourceLocation,
+    ) -> Self {
+        ServiceError::Parse {
+            format,
+            source: source.into(),
+            location: Some(location),
+        }
+    }
+
+    /// Como `parse_at`, mas exibe `message` no lugar da mensagem do parser, que
+    /// normalmente já repete a posição.
+    pub(crate) fn syntax(
+        format: &'static str,
+        message: impl Into<String>,
+        source: impl Into<BoxError>,
+        location: SourceLocation,
+    ) -> Self {
+        let source = SyntaxError {
+            message: message.into(),
+            source: source.into(),
+        };
+        Self::parse_at(format, source, location)
+    }
+
+    pub fn serialize(format: &'static str, source: impl Into<BoxError>) -> Self {
+        ServiceError::Serialize {
+            format,
+            source: source.into(),
+        }
+    }
+
+    pub fn encoding(source: impl Into<BoxError>) -> Self {
+        ServiceError::Encoding {
+            source: source.into(),
+        }
+    }
+
+    pub fn unsupported_shape(format: &'static str, message: impl Into<String>) -> Self {
+        ServiceError::UnsupportedShape {
+            format,
+            message: message.into(),
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        ServiceError::Validation {
+            message: message.into(),
+        }
+    }
+
+    /// Posição do erro na entrada, quando o parser a informa.
+    pub fn location(&self) -> Option<SourceLocation> {
+        match self {
+            ServiceError::Parse { location, .. } => *location,
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::Parse {
+                format,
+                source,
+                location: Some(loc),
+            } => write!(
+                f,
+                "failed to parse {} at line {}, column {}: {}",
+                format, loc.line, loc.column, source
+            ),
+            ServiceError::Parse {
+                format,
+                source,
+                location: None,
+            } => write!(f, "failed to parse {}: {}", format, source),
+            ServiceError::Serialize { format, source } => {
+                write!(f, "failed to write {}: {}", format, source)
+            }
+            ServiceError::Encoding { source } => write!(f, "invalid text encoding: {}", source),
+            ServiceError::UnsupportedShape { format, message } => {
+                write!(f, "{} cannot represent this document: {}", format, message)
+            }
+            ServiceError::Http { source } => write!(f, "HTTP request failed: {}", source),
+            ServiceError::Validation { message } => f.write_str(message),
+        }
+    }
+}
+
+impl Error for ServiceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ServiceError::Parse { source, .. }
+            | ServiceError::Serialize { source, .. }
+            | ServiceError::Encoding { source } => Some(source.as_ref()),
+            ServiceError::Http { source } => Some(source),
+            ServiceError::UnsupportedShape { .. } | ServiceError::Validation { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ServiceError {
+    fn from(source: reqwest::Error) -> Self {
+        ServiceError::Http { source }
+    }
+}
@@ -0,0 +1,20 @@
+use md5::{Digest, Md5};
+use sha1::Sha1;
+use sha2::Sha256;
+
+use crate::enums::hash_algorithm::HashAlgorithm;
+
+pub fn hash_bytes(data: &[u8], algorithm: HashAlgorithm) -> Vec<u8> {
+    match algorithm {
+        HashAlgorithm::Md5 => Md5::digest(data).to_vec(),
+        HashAlgorithm::Sha1 => Sha1::digest(data).to_vec(),
+        HashAlgorithm::Sha256 => Sha256::digest(data).to_vec(),
+    }
+}
+
+pub fn hash_hex(data: &[u8], algorithm: HashAlgorithm) -> String {
+    hash_bytes(data, algorithm)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
@@ -0,0 +1,171 @@
+use serde_json::Value;
+
+use crate::enums::format_converter::FormatConverter;
+use crate::enums::query_language::QueryLanguage;
+use crate::services::csv_converter::CsvFormat;
+use crate::services::error::ServiceError;
+use crate::services::format_detector::{self, AutoFormat};
+use crate::services::json_converter::JsonFormat;
+use crate::services::json_path;
+use crate::services::json5_converter::Json5Format;
+use crate::services::ndjson_converter::NdjsonFormat;
+use crate::services::rust_generator::RustFormat;
+use crate::services::schema_inference::JsonSchemaFormat;
+use crate::services::toml_converter::TomlFormat;
+use crate::services::typescript_generator::TypeScriptFormat;
+use crate::services::xml_converter::XmlFormat;
+use crate::services::xpath;
+use crate::services::yaml_converter::YamlFormat;
+use crate::structs::conversion_result::ConversionResult;
+use crate::structs::converter_options::ConverterOptions;
+
+/// Formato de texto suportado pelo conversor.
+///
+/// Toda conversão passa por um `serde_json::Value` intermediário: a entrada é
+/// lida com `parse` e a saída é gerada com `emit`, então qualquer formato do
+/// registro converte para qualquer outro.
+pub trait Format: Sync {
+    fn kind(&self) -> FormatConverter;
+    /// Nome exibido na interface (ComboBox) e aceito pela linha de comando.
+    fn name(&self) -> &'static str;
+    fn parse(&self, input: &str, options: &ConverterOptions) -> Result<Value, ServiceError>;
+    /// Lê todos os documentos de um fluxo. Formatos de documento único usam `parse`.
+    fn parse_documents(
+        &self,
+        input: &str,
+        options: &ConverterOptions,
+    ) -> Result<Vec<Value>, ServiceError> {
+        self.parse(input, options).map(|v| vec![v])
+    }
+    fn emit(&self, value: &Value, options: &ConverterOptions) -> Result<String, ServiceError>;
+    /// Formatos só de entrada (ex.: `Auto`) ficam fora das opções de saída.
+    fn can_emit(&self) -> bool {
+        true
+    }
+    /// Formatos só de saída (ex.: `JsonSchema`) ficam fora das opções de entrada.
+    fn can_parse(&self) -> bool {
+        true
+    }
+    /// Extensões de arquivo do formato; a primeira é usada ao salvar.
+    fn extensions(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+// A ordem do registro define a ordem dos ComboBox da interface. Cada ComboBox
+// lista só os formatos do seu lado, então os índices vêm de `input_formats` e
+// `output_formats`, não do registro.
+static REGISTRY: [&dyn Format; 11] = [
+    &JsonFormat,
+    &CsvFormat,
+    &YamlFormat,
+    &XmlFormat,
+    &TomlFormat,
+    &NdjsonFormat,
+    &Json5Format,
+    &JsonSchemaFormat,
+    &RustFormat,
+    &TypeScriptFormat,
+    &AutoFormat,
+];
+
+pub fn registry() -> &'static [&'static dyn Format] {
+    &REGISTRY
+}
+
+/// Formatos aceitos na entrada, na ordem do ComboBox.
+pub fn input_formats() -> impl Iterator<Item = &'static dyn Format> {
+    REGISTRY.iter().copied().filter(|f| f.can_parse())
+}
+
+/// Formatos aceitos na saída, na ordem do ComboBox.
+pub fn output_formats() -> impl Iterator<Item = &'static dyn Format> {
+    REGISTRY.iter().copied().filter(|f| f.can_emit())
+}
+
+pub fn lookup(kind: FormatConverter) -> &'static dyn Format {
+    REGISTRY
+        .iter()
+        .copied()
+        .find(|f| f.kind() == kind)
+        .expect("Formato sem implementação registrada")
+}
+
+pub fn convert(
+    input: &str,
+    from: FormatConverter,
+    to: FormatConverter,
+    options: &ConverterOptions,
+) -> Result<ConversionResult, ServiceError> {
+    let query = options.query.as_deref().filter(|q| !q.trim().is_empty());
+    if let Some(expression) = query
+        && options.query_language == QueryLanguage::XPath
+    {
+        return select_xpath(input, from, expression);
+    }
+
+    // Em `Auto` a detecção é feita aqui para devolver o formato encontrado
+    let (docs, detected) = if from == FormatConverter::Auto {
+        let detection = format_detector::detect_or_fail(input)?;
+        let docs = format_detector::parse_detected(input, &detection, options)?;
+        (docs, Some(detection))
+    } else {
+        (from.format().parse_documents(input, options)?, None)
+    };
+    let documents = docs.len();
+    let mut value = merge_documents(docs);
+    if let Some(expression) = query {
+        value = json_path::apply(&value, expression)?;
+    }
+    let output = to.format().emit(&value, options)?;
+    Ok(ConversionResult {
+        output,
+        documents,
+        detected,
+        matches: None,
+    })
+}
+
+/// Lê a entrada como um único valor; fluxos com vários documentos viram um array.
+pub fn parse_input(
+    input: &str,
+    from: FormatConverter,
+    options: &ConverterOptions,
+) -> Result<Value, ServiceError> {
+    from.format()
+        .parse_documents(input, options)
+        .map(merge_documents)
+}
+
+// O XPath consulta o próprio XML, sem passar pelo valor intermediário
+fn select_xpath(
+    input: &str,
+    from: FormatConverter,
+    expression: &str,
+) -> Result<ConversionResult, ServiceError> {
+    let detected = match from {
+        FormatConverter::Auto => Some(format_detector::detect_or_fail(input)?),
+        _ => None,
+    };
+    if detected.map_or(from, |d| d.format) != FormatConverter::Xml {
+        return Err(ServiceError::validation("XPath queries require XML input"));
+    }
+    let matches = xpath::select(input, expression)?;
+    // Número, texto ou booleano vem como um único item sem caminho
+    let node_set = matches.first().is_none_or(|m| !m.path.is_empty());
+    Ok(ConversionResult {
+        output: xpath::format_matches(&matches),
+        documents: 1,
+        detected,
+        matches: node_set.then_some(matches.len()),
+    })
+}
+
+/// Um único documento vira o próprio valor; vários viram um array, na ordem do fluxo.
+pub fn merge_documents(mut docs: Vec<Value>) -> Value {
+    if docs.len() == 1 {
+        docs.remove(0)
+    } else {
+        Value::Array(docs)
+    }
+}
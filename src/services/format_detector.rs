@@ -0,0 +1,201 @@
+use serde_json::Value;
+
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::ServiceError;
+use crate::services::format::{self, Format};
+use crate::services::{
+    json_converter, json5_converter, ndjson_converter, toml_converter, xml_converter,
+    yaml_converter,
+};
+use crate::structs::converter_options::ConverterOptions;
+use crate::structs::format_detection::FormatDetection;
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+
+// Delimitadores testados no CSV, em ordem de preferência em caso de empate
+const CSV_DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+// Linhas analisadas para adivinhar o delimitador
+const CSV_SAMPLE_LINES: usize = 20;
+
+/// Formato só de entrada: identifica o texto com `detect_format` e lê com o
+/// formato encontrado.
+pub struct AutoFormat;
+
+impl Format for AutoFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::Auto
+    }
+
+    fn name(&self) -> &'static str {
+        "Auto"
+    }
+
+    fn can_emit(&self) -> bool {
+        false
+    }
+
+    fn parse(&self, input: &str, options: &ConverterOptions) -> Result<Value, ServiceError> {
+        let detection = detect_or_fail(input)?;
+        parse_detected(input, &detection, options).map(format::merge_documents)
+    }
+
+    fn parse_documents(
+        &self,
+        input: &str,
+        options: &ConverterOptions,
+    ) -> Result<Vec<Value>, ServiceError> {
+        parse_detected(input, &detect_or_fail(input)?, options)
+    }
+
+    fn emit(&self, _value: &Value, _options: &ConverterOptions) -> Result<String, ServiceError> {
+        Err(ServiceError::validation(
+            "Auto can only be used as an input format",
+        ))
+    }
+}
+
+pub(crate) fn detect_or_fail(input: &str) -> Result<FormatDetection, ServiceError> {
+    detect_format(input)
+        .ok_or_else(|| ServiceError::validation("could not detect the input format"))
+}
+
+/// Lê a entrada com o formato detectado, usando o delimitador CSV adivinhado.
+pub(crate) fn parse_detected(
+    input: &str,
+    detection: &FormatDetection,
+    options: &ConverterOptions,
+) -> Result<Vec<Value>, ServiceError> {
+    match detection.csv_delimiter {
+        Some(delimiter) => {
+            let mut options = options.clone();
+            options.csv.delimiter = delimiter;
+            detection.format.format().parse_documents(input, &options)
+        }
+        None => detection.format.format().parse_documents(input, options),
+    }
+}
+
+/// Identifica se o texto é JSON, NDJSON, JSON5, XML, TOML, YAML ou CSV.
+///
+/// Cada formato recebe uma confiança e vence a maior. Qualquer texto é um YAML
+/// válido (um escalar), então YAML só tem confiança alta quando a raiz é um
+/// mapa ou uma lista.
+pub fn detect_format(input: &str) -> Option<FormatDetection> {
+    let trimmed = input.trim_start_matches('\u{feff}').trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let mut candidates = Vec::new();
+    let detection = |format, confidence| FormatDetection {
+        format,
+        confidence,
+        csv_delimiter: None,
+    };
+
+    let valid_json =
+        trimmed.starts_with(['{', '[']) && json_converter::json_to_value(trimmed).is_ok();
+    if trimmed.starts_with(['{', '[']) {
+        let confidence = if valid_json { 1.0 } else { 0.6 };
+        candidates.push(detection(FormatConverter::Json, confidence));
+    }
+
+    // Várias linhas com um valor JSON cada, que o JSON comum rejeitaria
+    if trimmed.starts_with(['{', '['])
+        && trimmed
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .nth(1)
+            .is_some()
+        && ndjson_converter::ndjson_to_value(trimmed).is_ok()
+    {
+        candidates.push(detection(FormatConverter::Ndjson, 0.95));
+    }
+
+    // JSON com comentários, vírgulas finais ou chaves sem aspas; só é testado
+    // quando o JSON comum falha, com o parser que limita a profundidade
+    if !valid_json
+        && trimmed.starts_with(['{', '[', '/'])
+        && json5_converter::json5_to_value(trimmed).is_ok()
+    {
+        candidates.push(detection(FormatConverter::Json5, 0.9));
+    }
+
+    if trimmed.starts_with('<') {
+        let confidence = if xml_converter::xml_to_value(trimmed, &XmlOptions::default()).is_ok() {
+            1.0
+        } else {
+            0.6
+        };
+        candidates.push(detection(FormatConverter::Xml, confidence));
+    }
+
+    // Exige ao menos um `chave = valor` ou `[tabela]` para não aceitar texto vazio de TOML
+    let looks_like_toml = trimmed.lines().map(str::trim).any(|l| {
+        (l.starts_with('[') && l.ends_with(']'))
+            || l.split_once('=').is_some_and(|(k, _)| !k.trim().is_empty())
+    });
+    if looks_like_toml && toml_converter::toml_to_value(trimmed).is_ok() {
+        candidates.push(detection(FormatConverter::Toml, 0.9));
+    }
+
+    if let Ok(value) = yaml_converter::yaml_to_value(trimmed, &YamlOptions::default()) {
+        let confidence = match value {
+            Value::Object(_) | Value::Array(_) => 0.85,
+            _ => 0.2,
+        };
+        candidates.push(detection(FormatConverter::Yaml, confidence));
+    }
+
+    if let Some((delimiter, confidence)) = sniff_csv_delimiter(trimmed) {
+        candidates.push(FormatDetection {
+            format: FormatConverter::Csv,
+            confidence,
+            csv_delimiter: Some(delimiter),
+        });
+    }
+
+    // Em empate, vence o primeiro testado
+    candidates.into_iter().reduce(|best, c| {
+        if c.confidence > best.confidence {
+            c
+        } else {
+            best
+        }
+    })
+}
+
+// O delimitador precisa aparecer o mesmo número de vezes em todas as linhas da amostra
+fn sniff_csv_delimiter(text: &str) -> Option<(u8, f32)> {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .take(CSV_SAMPLE_LINES)
+        .collect();
+    let mut best: Option<(u8, usize)> = None;
+    for delimiter in CSV_DELIMITERS {
+        let counts: Vec<usize> = lines.iter().map(|l| count_unquoted(l, delimiter)).collect();
+        let first = counts[0];
+        if first == 0 || counts.iter().any(|c| *c != first) {
+            continue;
+        }
+        if best.is_none_or(|(_, count)| first > count) {
+            best = Some((delimiter, first));
+        }
+    }
+    let confidence = if lines.len() > 1 { 0.8 } else { 0.4 };
+    best.map(|(delimiter, _)| (delimiter, confidence))
+}
+
+fn count_unquoted(line: &str, delimiter: u8) -> usize {
+    let mut quoted = false;
+    let mut count = 0;
+    for b in line.bytes() {
+        if b == b'"' {
+            quoted = !quoted;
+        } else if b == delimiter && !quoted {
+            count += 1;
+        }
+    }
+    count
+}
@@ -2,9 +2,10 @@ use std::collections::HashMap;
 
 use reqwest::Client;
 
+use crate::services::error::ServiceError;
 use crate::structs::{request_data::RequestData, request_result::RequestResult};
 
-pub fn parse_heredoc_request(input: &str) -> Result<RequestData, String> {
+pub fn parse_heredoc_request(input: &str) -> Result<RequestData, ServiceError> {
     let mut method: Option<String> = None;
     let mut url: Option<String> = None;
     let mut headers = HashMap::new();
@@ -46,23 +47,25 @@ pub fn parse_heredoc_request(input: &str) -> Result<RequestData, String> {
     }
 
     // 🔍 Validação obrigatória
-    let method = method.ok_or("Bloco obrigatório <<METHOD ... METHOD>> ausente")?;
-    let url = url.ok_or("Bloco obrigatório <<URL ... URL>> ausente")?;
+    let method = method
+        .ok_or_else(|| ServiceError::validation("missing required block <<METHOD ... METHOD>>"))?;
+    let url =
+        url.ok_or_else(|| ServiceError::validation("missing required block <<URL ... URL>>"))?;
 
     // validação de URL simples
     if !(url.starts_with("http://") || url.starts_with("https://")) {
-        return Err(format!(
-            "URL inválida: '{url}' (deve começar com http:// ou https://)"
-        ));
+        return Err(ServiceError::validation(format!(
+            "invalid URL: '{url}' (must start with http:// or https://)"
+        )));
     }
 
     // validação do método HTTP simples
     let valid_methods = ["GET", "POST", "PUT", "DELETE", "PATCH"];
     if !valid_methods.contains(&method.to_uppercase().as_str()) {
-        return Err(format!(
-            "Método HTTP inválido: '{}' (válidos: GET, POST, PUT, DELETE, PATCH)",
+        return Err(ServiceError::validation(format!(
+            "invalid HTTP method: '{}' (valid: GET, POST, PUT, DELETE, PATCH)",
             method
-        ));
+        )));
     }
 
     Ok(RequestData {
@@ -73,17 +76,18 @@ pub fn parse_heredoc_request(input: &str) -> Result<RequestData, String> {
     })
 }
 
-pub async fn send_request(req: &RequestData) -> Result<RequestResult, String> {
+pub async fn send_request(req: &RequestData) -> Result<RequestResult, ServiceError> {
     // configura cliente HTTP
     let client_builder = Client::builder();
 
-    let client = client_builder
-        .build()
-        .map_err(|e| format!("client build error: {:?}", e))?;
+    let client = client_builder.build()?;
 
     // define método e URL (normalizado em maiúsculas)
     let method = req.method.as_deref().unwrap_or("GET").to_uppercase();
-    let url = req.url.as_deref().ok_or("Missing URL")?;
+    let url = req
+        .url
+        .as_deref()
+        .ok_or_else(|| ServiceError::validation("missing URL"))?;
 
     let mut request = match method.to_uppercase().as_str() {
         "POST" => client.post(url),
@@ -107,7 +111,7 @@ pub async fn send_request(req: &RequestData) -> Result<RequestResult, String> {
     }
 
     // envia e processa resposta
-    let response = request.send().await.map_err(|e| e.to_string())?;
+    let response = request.send().await?;
 
     let status_code = response.status().as_u16();
     let mut headers_map = HashMap::new();
@@ -116,7 +120,7 @@ pub async fn send_request(req: &RequestData) -> Result<RequestResult, String> {
         headers_map.insert(k.to_string(), v.to_str().unwrap_or("").to_string());
     }
 
-    let body = response.text().await.map_err(|e| e.to_string())?;
+    let body = response.text().await?;
 
     Ok(RequestResult {
         status_code,
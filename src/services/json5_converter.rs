@@ -0,0 +1,373 @@
+use serde_json::{Map, Number, Value};
+
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::{ServiceError, SourceLocation};
+use crate::services::format::Format;
+use crate::services::{csv_converter, json_converter, xml_converter, yaml_converter};
+use crate::structs::converter_options::ConverterOptions;
+use crate::structs::csv_options::CsvOptions;
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+
+/// JSON tolerante (JSON5 e JSONC): aceita comentários, vírgulas finais, aspas
+/// simples e chaves sem aspas. A saída é JSON comum, que também é JSON5 válido.
+pub struct Json5Format;
+
+impl Format for Json5Format {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::Json5
+    }
+
+    fn name(&self) -> &'static str {
+        "JSON5"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["json5", "jsonc"]
+    }
+
+    fn parse(&self, input: &str, _options: &ConverterOptions) -> Result<Value, ServiceError> {
+        json5_to_value(input)
+    }
+
+    fn emit(&self, value: &Value, _options: &ConverterOptions) -> Result<String, ServiceError> {
+        json_converter::value_to_json(value)
+    }
+}
+
+pub fn json5_to_value(json5_str: &str) -> Result<Value, ServiceError> {
+    let mut parser = Json5Parser {
+        text: json5_str,
+        pos: 0,
+        depth: 0,
+    };
+    parser.skip_whitespace()?;
+    let value = parser.parse_value()?;
+    parser.skip_whitespace()?;
+    if parser.pos < json5_str.len() {
+        return Err(parser.error("trailing characters after the document"));
+    }
+    Ok(value)
+}
+
+struct Json5Parser<'a> {
+    text: &'a str,
+    /// Posição atual em bytes.
+    pos: usize,
+    /// Objetos e arrays abertos, limitado como no serde_json para não estourar a pilha.
+    depth: usize,
+}
+
+const MAX_DEPTH: usize = 128;
+
+impl Json5Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ServiceError {
+        ServiceError::parse_at(
+            "JSON5",
+            message.into(),
+            SourceLocation::from_offset(self.text, self.pos),
+        )
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ServiceError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected '{}', found '{}'", expected, c))),
+            None => Err(self.error(format!("expected '{}', found end of input", expected))),
+        }
+    }
+
+    // Espaços (incluindo BOM e separadores Unicode) e comentários `//` e `/* */`
+    fn skip_whitespace(&mut self) -> Result<(), ServiceError> {
+        loop {
+            let rest = &self.text[self.pos..];
+            if rest.starts_with("//") {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                match comment.find("*/") {
+                    Some(end) => self.pos += end + 4,
+                    None => return Err(self.error("unterminated block comment")),
+                }
+            } else if self
+                .peek()
+                .is_some_and(|c| c.is_whitespace() || c == '\u{feff}')
+            {
+                self.bump();
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, ServiceError> {
+        match self.peek() {
+            Some('{') => self.nested(Self::parse_object),
+            Some('[') => self.nested(Self::parse_array),
+            Some(quote @ ('"' | '\'')) => self.parse_string(quote).map(Value::String),
+            Some(c) if c.is_ascii_digit() || matches!(c, '+' | '-' | '.') => self.parse_number(),
+            Some(c) if is_identifier_start(c) => {
+                let start = self.pos;
+                match self.parse_identifier().as_str() {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "null" => Ok(Value::Null),
+                    "Infinity" | "NaN" => {
+                        self.pos = start;
+                        Err(self.error("Infinity and NaN cannot be represented in JSON"))
+                    }
+                    other => {
+                        self.pos = start;
+                        Err(self.error(format!("unexpected identifier '{}'", other)))
+                    }
+                }
+            }
+            Some(c) => Err(self.error(format!("unexpected character '{}'", c))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Value, ServiceError>,
+    ) -> Result<Value, ServiceError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("recursion limit exceeded"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_object(&mut self) -> Result<Value, ServiceError> {
+        self.expect('{')?;
+        let mut map = Map::new();
+        loop {
+            self.skip_whitespace()?;
+            let key = match self.peek() {
+                Some('}') => {
+                    self.bump();
+                    break;
+                }
+                Some(quote @ ('"' | '\'')) => self.parse_string(quote)?,
+                Some(c) if is_identifier_start(c) => self.parse_identifier(),
+                Some(c) => return Err(self.error(format!("expected a key, found '{}'", c))),
+                None => return Err(self.error("unterminated object")),
+            };
+            self.skip_whitespace()?;
+            self.expect(':')?;
+            self.skip_whitespace()?;
+            let value = self.parse_value()?;
+            map.insert(key, value);
+            self.skip_whitespace()?;
+            // A vírgula antes de `}` é opcional (vírgula final)
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some('}') => {
+                    self.bump();
+                    break;
+                }
+                _ => return Err(self.error("expected ',' or '}' in object")),
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn parse_array(&mut self) -> Result<Value, ServiceError> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            if self.peek() == Some(']') {
+                self.bump();
+                break;
+            }
+            items.push(self.parse_value()?);
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some(']') => {
+                    self.bump();
+                    break;
+                }
+                _ => return Err(self.error("expected ',' or ']' in array")),
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn parse_identifier(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| is_identifier_start(c) || c.is_alphanumeric())
+        {
+            self.bump();
+        }
+        self.text[start..self.pos].to_string()
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<String, ServiceError> {
+        self.bump();
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                Some(c) if c == quote => return Ok(out),
+                Some('\\') => self.parse_escape(&mut out)?,
+                Some('\n' | '\r') | None => return Err(self.error("unterminated string")),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self, out: &mut String) -> Result<(), ServiceError> {
+        match self.bump() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some('v') => out.push('\u{b}'),
+            Some('0') => out.push('\0'),
+            Some('x') => {
+                let code = self.parse_hex(2)?;
+                out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            Some('u') => {
+                let mut code = self.parse_hex(4)?;
+                // Par substituto (surrogate pair) em dois escapes `\u`
+                if (0xD800..0xDC00).contains(&code) && self.text[self.pos..].starts_with("\\u") {
+                    self.pos += 2;
+                    let low = self.parse_hex(4)?;
+                    code = 0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+                }
+                out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            // Barra invertida no fim da linha continua a string na linha seguinte
+            Some('\r') => {
+                if self.peek() == Some('\n') {
+                    self.bump();
+                }
+            }
+            Some('\n' | '\u{2028}' | '\u{2029}') => {}
+            Some(c) => out.push(c),
+            None => return Err(self.error("unterminated string")),
+        }
+        Ok(())
+    }
+
+    fn parse_hex(&mut self, digits: usize) -> Result<u32, ServiceError> {
+        let hex = self
+            .text
+            .get(self.pos..self.pos + digits)
+            .filter(|h| h.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid escape sequence"))?;
+        self.pos += digits;
+        Ok(u32::from_str_radix(hex, 16).unwrap_or(0))
+    }
+
+    // Aceita hexadecimal, sinal `+` e ponto decimal no início ou no fim (`.5`, `5.`)
+    fn parse_number(&mut self) -> Result<Value, ServiceError> {
+        let start = self.pos;
+        let negative = match self.peek() {
+            Some('-') => {
+                self.bump();
+                true
+            }
+            Some('+') => {
+                self.bump();
+                false
+            }
+            _ => false,
+        };
+        let digits_start = self.pos;
+        let mut previous = None;
+        while let Some(c) = self.peek() {
+            let exponent_sign = matches!(c, '+' | '-') && matches!(previous, Some('e' | 'E'));
+            if !(c.is_ascii_alphanumeric() || c == '.' || exponent_sign) {
+                break;
+            }
+            previous = Some(c);
+            self.bump();
+        }
+        let text = self.text;
+        let digits = &text[digits_start..self.pos];
+        let lexeme = &text[start..self.pos];
+        let invalid = |parser: &mut Self| {
+            parser.pos = start;
+            Err(parser.error(format!("invalid number '{}'", lexeme)))
+        };
+
+        if digits == "Infinity" || digits == "NaN" {
+            self.pos = start;
+            return Err(self.error("Infinity and NaN cannot be represented in JSON"));
+        }
+        if let Some(hex) = digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+        {
+            let Ok(n) = i64::from_str_radix(hex, 16) else {
+                return invalid(self);
+            };
+            return Ok(Value::from(if negative { -n } else { n }));
+        }
+        if !digits.contains(['.', 'e', 'E'])
+            && let Ok(n) = digits.parse::<u64>()
+        {
+            if !negative {
+                return Ok(Value::from(n));
+            }
+            if let Ok(n) = i64::try_from(n) {
+                return Ok(Value::from(-n));
+            }
+        }
+        match digits.parse::<f64>() {
+            Ok(f) if digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') => {
+                let f = if negative { -f } else { f };
+                match Number::from_f64(f) {
+                    Some(n) => Ok(Value::Number(n)),
+                    None => invalid(self),
+                }
+            }
+            _ => invalid(self),
+        }
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+pub fn json5_to_json(json5_str: &str) -> Result<String, ServiceError> {
+    json5_to_value(json5_str).and_then(|v| json_converter::value_to_json(&v))
+}
+
+pub fn json5_to_csv(json5_str: &str) -> Result<String, ServiceError> {
+    json5_to_value(json5_str).and_then(|v| csv_converter::value_to_csv(&v, &CsvOptions::default()))
+}
+
+pub fn json5_to_yaml(json5_str: &str) -> Result<String, ServiceError> {
+    json5_to_value(json5_str)
+        .and_then(|v| yaml_converter::value_to_yaml(&v, &YamlOptions::default()))
+}
+
+pub fn json5_to_xml(json5_str: &str) -> Result<String, ServiceError> {
+    json5_to_value(json5_str).and_then(|v| xml_converter::value_to_xml(&v, &XmlOptions::default()))
+}
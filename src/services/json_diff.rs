@@ -0,0 +1,221 @@
+// Diferença estrutural entre dois documentos já lidos pelo conversor, então a
+// comparação independe do formato de origem (ex.: YAML contra JSON). Objetos
+// são comparados por chave, arrays por índice (ou como multiconjuntos com
+// `ignore_array_order`) e números pelo valor, então `1` e `1.0` são iguais.
+
+use std::cmp::Reverse;
+
+use serde_json::{Map, Value};
+
+use crate::enums::diff_kind::DiffKind;
+use crate::structs::diff_entry::DiffEntry;
+use crate::structs::diff_options::DiffOptions;
+
+/// Caminhos adicionados, removidos e alterados de `left` para `right`, na ordem
+/// em que aparecem nos documentos.
+pub fn diff(left: &Value, right: &Value, options: &DiffOptions) -> Vec<DiffEntry> {
+    let mut entries = Vec::new();
+    diff_into(left, right, "$", options, &mut entries);
+    entries
+}
+
+/// Uma linha por diferença: `+` adicionado, `-` removido e `~` alterado, com os
+/// valores em JSON compacto.
+pub fn format_diff(entries: &[DiffEntry]) -> String {
+    let compact = |v: &Option<Value>| {
+        v.as_ref()
+            .map(|v| serde_json::to_string(v).unwrap_or_default())
+            .unwrap_or_default()
+    };
+    entries
+        .iter()
+        .map(|e| {
+            let values = match e.kind {
+                DiffKind::Added => compact(&e.right),
+                DiffKind::Removed => compact(&e.left),
+                DiffKind::Changed => format!("{} → {}", compact(&e.left), compact(&e.right)),
+            };
+            format!("{} {}: {}", e.kind.symbol(), e.path, values)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn diff_into(
+    left: &Value,
+    right: &Value,
+    path: &str,
+    options: &DiffOptions,
+    out: &mut Vec<DiffEntry>,
+) {
+    match (left, right) {
+        (Value::Object(a), Value::Object(b)) => diff_objects(a, b, path, options, out),
+        (Value::Array(a), Value::Array(b)) if options.ignore_array_order => {
+            diff_unordered(a, b, path, options, out)
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let item_path = index_path(path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(x), Some(y)) => diff_into(x, y, &item_path, options, out),
+                    (Some(x), None) => out.push(removed(item_path, x)),
+                    (None, Some(y)) => out.push(added(item_path, y)),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if values_equal(left, right, options) => {}
+        _ => out.push(DiffEntry {
+            kind: DiffKind::Changed,
+            path: path.to_string(),
+            left: Some(left.clone()),
+            right: Some(right.clone()),
+        }),
+    }
+}
+
+fn diff_objects(
+    a: &Map<String, Value>,
+    b: &Map<String, Value>,
+    path: &str,
+    options: &DiffOptions,
+    out: &mut Vec<DiffEntry>,
+) {
+    for (key, x) in a {
+        let key_path = key_path(path, key);
+        match b.get(key) {
+            Some(y) => diff_into(x, y, &key_path, options, out),
+            None => out.push(removed(key_path, x)),
+        }
+    }
+    for (key, y) in b {
+        if !a.contains_key(key) {
+            out.push(added(key_path(path, key), y));
+        }
+    }
+}
+
+// Casa primeiro os itens iguais; os que sobram são comparados em pares com o
+// objeto ou array mais parecido (para mostrar só o campo alterado) e, nos demais
+// casos, viram removidos (índice do primeiro documento) e adicionados (índice do
+// segundo)
+fn diff_unordered(
+    a: &[Value],
+    b: &[Value],
+    path: &str,
+    options: &DiffOptions,
+    out: &mut Vec<DiffEntry>,
+) {
+    let mut matched = vec![false; b.len()];
+    let mut unmatched_left = Vec::new();
+    for (i, x) in a.iter().enumerate() {
+        let found = (0..b.len()).find(|&j| !matched[j] && values_equal(x, &b[j], options));
+        match found {
+            Some(j) => matched[j] = true,
+            None => unmatched_left.push(i),
+        }
+    }
+    let mut unmatched_right: Vec<usize> = (0..b.len()).filter(|&j| !matched[j]).collect();
+
+    for i in unmatched_left {
+        let pair = unmatched_right
+            .iter()
+            .enumerate()
+            .filter(|(_, j)| same_container(&a[i], &b[**j]))
+            .max_by_key(|(p, j)| (similarity(&a[i], &b[**j], options), Reverse(*p)))
+            .map(|(p, _)| p);
+        match pair {
+            Some(p) => {
+                let j = unmatched_right.remove(p);
+                diff_into(&a[i], &b[j], &index_path(path, i), options, out);
+            }
+            None => out.push(removed(index_path(path, i), &a[i])),
+        }
+    }
+    for j in unmatched_right {
+        out.push(added(index_path(path, j), &b[j]));
+    }
+}
+
+fn same_container(a: &Value, b: &Value) -> bool {
+    matches!(
+        (a, b),
+        (Value::Object(_), Value::Object(_)) | (Value::Array(_), Value::Array(_))
+    )
+}
+
+// Quantidade de campos (ou itens, na mesma posição) com o mesmo valor
+fn similarity(a: &Value, b: &Value, options: &DiffOptions) -> usize {
+    match (a, b) {
+        (Value::Object(x), Value::Object(y)) => x
+            .iter()
+            .filter(|(k, v)| y.get(*k).is_some_and(|w| values_equal(v, w, options)))
+            .count(),
+        (Value::Array(x), Value::Array(y)) => x
+            .iter()
+            .zip(y)
+            .filter(|(v, w)| values_equal(v, w, options))
+            .count(),
+        _ => 0,
+    }
+}
+
+fn values_equal(a: &Value, b: &Value, options: &DiffOptions) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x == y || x.as_f64() == y.as_f64(),
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(k, v)| y.get(k).is_some_and(|w| values_equal(v, w, options)))
+        }
+        (Value::Array(x), Value::Array(y)) if x.len() != y.len() => false,
+        (Value::Array(x), Value::Array(y)) if options.ignore_array_order => {
+            let mut matched = vec![false; y.len()];
+            x.iter().all(|v| {
+                let found = (0..y.len()).find(|&j| !matched[j] && values_equal(v, &y[j], options));
+                found.map(|j| matched[j] = true).is_some()
+            })
+        }
+        (Value::Array(x), Value::Array(y)) => {
+            x.iter().zip(y).all(|(v, w)| values_equal(v, w, options))
+        }
+        _ => a == b,
+    }
+}
+
+fn added(path: String, value: &Value) -> DiffEntry {
+    DiffEntry {
+        kind: DiffKind::Added,
+        path,
+        left: None,
+        right: Some(value.clone()),
+    }
+}
+
+fn removed(path: String, value: &Value) -> DiffEntry {
+    DiffEntry {
+        kind: DiffKind::Removed,
+        path,
+        left: Some(value.clone()),
+        right: None,
+    }
+}
+
+fn index_path(path: &str, index: usize) -> String {
+    format!("{}[{}]", path, index)
+}
+
+// `.chave` para identificadores simples e `['chave']` nos demais casos
+fn key_path(path: &str, key: &str) -> String {
+    let simple = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if simple {
+        format!("{}.{}", path, key)
+    } else {
+        format!(
+            "{}['{}']",
+            path,
+            key.replace('\\', "\\\\").replace('\'', "\\'")
+        )
+    }
+}
@@ -0,0 +1,296 @@
+// JSON Patch (RFC 6902) e JSON Merge Patch (RFC 7396) sobre o `Value`
+// intermediário do conversor. Os caminhos usam JSON Pointer (RFC 6901).
+
+use serde_json::{Map, Value, json};
+
+use crate::services::error::ServiceError;
+
+/// Operações que transformam `from` em `to`. Arrays são comparados por índice:
+/// itens a mais viram `add` no fim e itens a menos viram `remove` do último
+/// para o primeiro, para os índices continuarem válidos.
+pub fn generate_patch(from: &Value, to: &Value) -> Value {
+    let mut operations = Vec::new();
+    generate_into(from, to, "", &mut operations);
+    Value::Array(operations)
+}
+
+fn generate_into(from: &Value, to: &Value, path: &str, out: &mut Vec<Value>) {
+    match (from, to) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, x) in a {
+                let key_path = format!("{}/{}", path, escape_token(key));
+                match b.get(key) {
+                    Some(y) => generate_into(x, y, &key_path, out),
+                    None => out.push(json!({ "op": "remove", "path": key_path })),
+                }
+            }
+            for (key, y) in b {
+                if !a.contains_key(key) {
+                    let key_path = format!("{}/{}", path, escape_token(key));
+                    out.push(json!({ "op": "add", "path": key_path, "value": y }));
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for (i, (x, y)) in a.iter().zip(b).enumerate() {
+                generate_into(x, y, &format!("{}/{}", path, i), out);
+            }
+            for (i, y) in b.iter().enumerate().skip(a.len()) {
+                out.push(json!({ "op": "add", "path": format!("{}/{}", path, i), "value": y }));
+            }
+            for i in (b.len()..a.len()).rev() {
+                out.push(json!({ "op": "remove", "path": format!("{}/{}", path, i) }));
+            }
+        }
+        _ if json_equal(from, to) => {}
+        _ => out.push(json!({ "op": "replace", "path": path, "value": to })),
+    }
+}
+
+/// Aplica as operações em ordem. A aplicação é atômica: se uma operação falhar
+/// (inclusive um `test` com valor diferente), nada é alterado e o erro indica
+/// o índice da operação.
+pub fn apply_patch(document: &Value, patch: &Value) -> Result<Value, ServiceError> {
+    let Value::Array(operations) = patch else {
+        return Err(ServiceError::validation(
+            "JSON Patch must be an array of operations",
+        ));
+    };
+    let mut result = document.clone();
+    for (index, operation) in operations.iter().enumerate() {
+        apply_operation(&mut result, operation).map_err(|message| {
+            let op = operation.get("op").and_then(Value::as_str).unwrap_or("?");
+            ServiceError::validation(format!(
+                "patch operation {} ({}) failed: {}",
+                index, op, message
+            ))
+        })?;
+    }
+    Ok(result)
+}
+
+fn apply_operation(document: &mut Value, operation: &Value) -> Result<(), String> {
+    let Value::Object(fields) = operation else {
+        return Err("operation must be an object".to_string());
+    };
+    let op = string_field(fields, "op")?;
+    let path = parse_pointer(string_field(fields, "path")?)?;
+    let value = || {
+        fields
+            .get("value")
+            .cloned()
+            .ok_or_else(|| "missing 'value'".to_string())
+    };
+
+    match op {
+        "add" => add(document, &path, value()?),
+        "remove" => remove(document, &path).map(|_| ()),
+        "replace" => {
+            let target = get_mut(document, &path)?;
+            *target = value()?;
+            Ok(())
+        }
+        "move" => {
+            let from = parse_pointer(string_field(fields, "from")?)?;
+            if path.len() > from.len() && path.starts_with(&from) {
+                return Err("cannot move a value into one of its children".to_string());
+            }
+            let moved = remove(document, &from)?;
+            add(document, &path, moved)
+        }
+        "copy" => {
+            let from = parse_pointer(string_field(fields, "from")?)?;
+            let copied = get_mut(document, &from)?.clone();
+            add(document, &path, copied)
+        }
+        "test" => {
+            let expected = value()?;
+            let actual = get_mut(document, &path)?;
+            if json_equal(actual, &expected) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "value at '{}' is {}, expected {}",
+                    pointer_to_string(&path),
+                    actual,
+                    expected
+                ))
+            }
+        }
+        other => Err(format!("unknown operation '{}'", other)),
+    }
+}
+
+fn string_field<'a>(fields: &'a Map<String, Value>, name: &str) -> Result<&'a str, String> {
+    match fields.get(name) {
+        Some(Value::String(s)) => Ok(s),
+        Some(_) => Err(format!("'{}' must be a string", name)),
+        None => Err(format!("missing '{}'", name)),
+    }
+}
+
+fn add(document: &mut Value, path: &[String], value: Value) -> Result<(), String> {
+    let Some((last, parent_path)) = path.split_last() else {
+        *document = value;
+        return Ok(());
+    };
+    match get_mut(document, parent_path)? {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+            Ok(())
+        }
+        Value::Array(items) => {
+            // `-` acrescenta no fim do array
+            let index = if last == "-" {
+                items.len()
+            } else {
+                array_index(last, items.len() + 1)?
+            };
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(format!(
+            "'{}' is not an object or array",
+            pointer_to_string(parent_path)
+        )),
+    }
+}
+
+fn remove(document: &mut Value, path: &[String]) -> Result<Value, String> {
+    let Some((last, parent_path)) = path.split_last() else {
+        return Err("cannot remove the whole document".to_string());
+    };
+    let missing = || format!("path '{}' does not exist", pointer_to_string(path));
+    match get_mut(document, parent_path)? {
+        Value::Object(map) => map.shift_remove(last).ok_or_else(missing),
+        Value::Array(items) => {
+            let index = array_index(last, items.len())?;
+            Ok(items.remove(index))
+        }
+        _ => Err(missing()),
+    }
+}
+
+fn get_mut<'a>(document: &'a mut Value, path: &[String]) -> Result<&'a mut Value, String> {
+    let mut current = document;
+    for (depth, token) in path.iter().enumerate() {
+        let next = match current {
+            Value::Object(map) => map.get_mut(token),
+            Value::Array(items) => {
+                let len = items.len();
+                array_index(token, len).ok().and_then(|i| items.get_mut(i))
+            }
+            _ => None,
+        };
+        current = next.ok_or_else(|| {
+            format!(
+                "path '{}' does not exist",
+                pointer_to_string(&path[..=depth])
+            )
+        })?;
+    }
+    Ok(current)
+}
+
+// Índice decimal sem zeros à esquerda e menor que `limit`
+fn array_index(token: &str, limit: usize) -> Result<usize, String> {
+    let valid = !token.is_empty()
+        && token.chars().all(|c| c.is_ascii_digit())
+        && (token == "0" || !token.starts_with('0'));
+    match token.parse::<usize>() {
+        Ok(index) if valid && index < limit => Ok(index),
+        _ => Err(format!("invalid array index '{}'", token)),
+    }
+}
+
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!(
+            "invalid JSON Pointer '{}': must start with '/'",
+            pointer
+        ));
+    };
+    Ok(rest
+        .split('/')
+        .map(|t| t.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn pointer_to_string(path: &[String]) -> String {
+    path.iter()
+        .map(|t| format!("/{}", escape_token(t)))
+        .collect()
+}
+
+// Igualdade do RFC 6902 (e do JSON Schema): números são comparados pelo valor
+pub(crate) fn json_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x == y || x.as_f64() == y.as_f64(),
+        (Value::Array(x), Value::Array(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(v, w)| json_equal(v, w))
+        }
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(k, v)| y.get(k).is_some_and(|w| json_equal(v, w)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Merge Patch que transforma `from` em `to`: chaves removidas viram `null` e
+/// valores que não são objetos substituem o original por inteiro. Um `null`
+/// presente em `to` não tem representação e também vira remoção.
+pub fn generate_merge_patch(from: &Value, to: &Value) -> Value {
+    match (from, to) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut patch = Map::new();
+            for key in a.keys() {
+                if !b.contains_key(key) {
+                    patch.insert(key.clone(), Value::Null);
+                }
+            }
+            for (key, y) in b {
+                match a.get(key) {
+                    Some(x) if json_equal(x, y) => {}
+                    Some(x) => {
+                        patch.insert(key.clone(), generate_merge_patch(x, y));
+                    }
+                    None => {
+                        patch.insert(key.clone(), y.clone());
+                    }
+                }
+            }
+            Value::Object(patch)
+        }
+        _ => to.clone(),
+    }
+}
+
+/// Aplica um Merge Patch: objetos são mesclados recursivamente, `null` remove a
+/// chave e qualquer outro valor substitui o original.
+pub fn apply_merge_patch(document: &Value, patch: &Value) -> Value {
+    let Value::Object(changes) = patch else {
+        return patch.clone();
+    };
+    let mut result = match document {
+        Value::Object(map) => map.clone(),
+        _ => Map::new(),
+    };
+    for (key, change) in changes {
+        if change.is_null() {
+            result.shift_remove(key);
+        } else {
+            let merged = apply_merge_patch(result.get(key).unwrap_or(&Value::Null), change);
+            result.insert(key.clone(), merged);
+        }
+    }
+    Value::Object(result)
+}
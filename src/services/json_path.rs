@@ -0,0 +1,563 @@
+// Subconjunto de JSONPath aplicado ao `Value` intermediário do conversor:
+// `$`, `.nome`, `['nome']`, `[0]`, `[-1]`, `[*]`, `.*`, `..nome`, uniões `[0,2]`,
+// fatias `[1:3]` e filtros `[?(@.preco < 10 && @.ativo)]`.
+
+use serde_json::Value;
+
+use crate::services::error::ServiceError;
+
+enum Selector {
+    Name(String),
+    Index(i64),
+    Wildcard,
+    Slice(Option<i64>, Option<i64>, i64),
+    Union(Vec<Selector>),
+    Filter(FilterExpr),
+}
+
+struct Segment {
+    /// `..`: aplica o seletor ao nó e a todos os descendentes.
+    recursive: bool,
+    selector: Selector,
+}
+
+enum FilterExpr {
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Exists(Operand),
+    Compare(Operand, CompareOp, Operand),
+}
+
+enum Operand {
+    /// Caminho relativo ao item (`@`) ou à raiz (`$`).
+    Path {
+        root: bool,
+        segments: Vec<Segment>,
+    },
+    Literal(Value),
+}
+
+#[derive(Clone, Copy)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Aplica a expressão e devolve o resultado como um único `Value`.
+///
+/// Caminhos que apontam para um só nó (sem `*`, `..`, fatias, uniões ou filtros)
+/// devolvem o próprio valor, ou null se ele não existir; os demais devolvem um
+/// array com todos os nós encontrados.
+pub fn apply(value: &Value, expression: &str) -> Result<Value, ServiceError> {
+    let segments = parse(expression)?;
+    let matches = select(value, value, &segments);
+    if segments.iter().all(Segment::is_definite) {
+        return Ok(matches.into_iter().next().cloned().unwrap_or(Value::Null));
+    }
+    Ok(Value::Array(matches.into_iter().cloned().collect()))
+}
+
+/// Todos os nós encontrados pela expressão, na ordem do documento.
+pub fn query<'a>(value: &'a Value, expression: &str) -> Result<Vec<&'a Value>, ServiceError> {
+    let segments = parse(expression)?;
+    Ok(select(value, value, &segments))
+}
+
+impl Segment {
+    fn is_definite(&self) -> bool {
+        !self.recursive && matches!(self.selector, Selector::Name(_) | Selector::Index(_))
+    }
+}
+
+fn parse(expression: &str) -> Result<Vec<Segment>, ServiceError> {
+    let mut parser = PathParser {
+        chars: expression.trim().chars().collect(),
+        pos: 0,
+    };
+    if !parser.eat('$') {
+        return Err(parser.error("expression must start with '$'"));
+    }
+    let segments = parser.parse_segments()?;
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("unexpected character"));
+    }
+    Ok(segments)
+}
+
+fn select<'a>(root: &'a Value, current: &'a Value, segments: &[Segment]) -> Vec<&'a Value> {
+    let mut nodes = vec![current];
+    for segment in segments {
+        let mut next = Vec::new();
+        for node in nodes {
+            if segment.recursive {
+                let mut descendants = Vec::new();
+                collect_descendants(node, &mut descendants);
+                for d in descendants {
+                    apply_selector(root, d, &segment.selector, &mut next);
+                }
+            } else {
+                apply_selector(root, node, &segment.selector, &mut next);
+            }
+        }
+        nodes = next;
+    }
+    nodes
+}
+
+fn collect_descendants<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(value);
+    match value {
+        Value::Array(items) => items.iter().for_each(|v| collect_descendants(v, out)),
+        Value::Object(map) => map.values().for_each(|v| collect_descendants(v, out)),
+        _ => {}
+    }
+}
+
+fn children(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(map) => map.values().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn apply_selector<'a>(
+    root: &'a Value,
+    node: &'a Value,
+    selector: &Selector,
+    out: &mut Vec<&'a Value>,
+) {
+    match selector {
+        Selector::Name(name) => {
+            if let Some(v) = node.as_object().and_then(|m| m.get(name)) {
+                out.push(v);
+            }
+        }
+        Selector::Index(index) => {
+            if let Some(items) = node.as_array()
+                && let Some(i) = resolve_index(*index, items.len())
+                && i < items.len()
+            {
+                out.push(&items[i]);
+            }
+        }
+        Selector::Wildcard => out.extend(children(node)),
+        Selector::Slice(start, end, step) => {
+            if let Some(items) = node.as_array() {
+                out.extend(slice_indexes(items.len(), *start, *end, *step).map(|i| &items[i]));
+            }
+        }
+        Selector::Union(selectors) => {
+            for s in selectors {
+                apply_selector(root, node, s, out);
+            }
+        }
+        Selector::Filter(filter) => {
+            out.extend(
+                children(node)
+                    .into_iter()
+                    .filter(|child| filter.matches(root, child)),
+            );
+        }
+    }
+}
+
+// Índices negativos contam a partir do fim
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    if index >= 0 {
+        usize::try_from(index).ok()
+    } else {
+        len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)
+    }
+}
+
+fn slice_indexes(
+    len: usize,
+    start: Option<i64>,
+    end: Option<i64>,
+    step: i64,
+) -> Box<dyn Iterator<Item = usize>> {
+    let len_i = len as i64;
+    let clamp = |i: i64| {
+        if i < 0 {
+            (len_i + i).max(0)
+        } else {
+            i.min(len_i)
+        }
+    };
+    if step > 0 {
+        let start = start.map_or(0, clamp);
+        let end = end.map_or(len_i, clamp);
+        Box::new((start..end).step_by(step as usize).map(|i| i as usize))
+    } else {
+        // Passo negativo percorre de trás para frente
+        let start = start.map_or(len_i - 1, |i| clamp(i).min(len_i - 1));
+        let end = end.map_or(-1, clamp);
+        Box::new(
+            ((end + 1)..=start)
+                .rev()
+                .step_by(step.unsigned_abs() as usize)
+                .map(|i| i as usize),
+        )
+    }
+}
+
+impl FilterExpr {
+    fn matches(&self, root: &Value, current: &Value) -> bool {
+        match self {
+            FilterExpr::Or(a, b) => a.matches(root, current) || b.matches(root, current),
+            FilterExpr::And(a, b) => a.matches(root, current) && b.matches(root, current),
+            FilterExpr::Not(e) => !e.matches(root, current),
+            FilterExpr::Exists(operand) => operand.resolve(root, current).is_some(),
+            FilterExpr::Compare(left, op, right) => {
+                match (left.resolve(root, current), right.resolve(root, current)) {
+                    (Some(l), Some(r)) => compare(&l, *op, &r),
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+impl Operand {
+    fn resolve(&self, root: &Value, current: &Value) -> Option<Value> {
+        match self {
+            Operand::Literal(v) => Some(v.clone()),
+            Operand::Path {
+                root: true,
+                segments,
+            } => select(root, root, segments).first().map(|v| (*v).clone()),
+            Operand::Path { segments, .. } => select(root, current, segments)
+                .first()
+                .map(|v| (*v).clone()),
+        }
+    }
+}
+
+fn compare(left: &Value, op: CompareOp, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match op {
+        CompareOp::Eq => numbers_or_values_equal(left, right),
+        CompareOp::Ne => !numbers_or_values_equal(left, right),
+        CompareOp::Lt => ordering.is_some_and(|o| o.is_lt()),
+        CompareOp::Le => ordering.is_some_and(|o| o.is_le()),
+        CompareOp::Gt => ordering.is_some_and(|o| o.is_gt()),
+        CompareOp::Ge => ordering.is_some_and(|o| o.is_ge()),
+    }
+}
+
+// `1 == 1.0` deve ser verdadeiro, o que a igualdade de `Value` não garante
+fn numbers_or_values_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => left == right,
+    }
+}
+
+struct PathParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PathParser {
+    fn error(&self, message: &str) -> ServiceError {
+        ServiceError::validation(format!(
+            "invalid JSONPath at position {}: {}",
+            self.pos + 1,
+            message
+        ))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let len = s.chars().count();
+        if self.chars[self.pos..]
+            .iter()
+            .take(len)
+            .copied()
+            .eq(s.chars())
+        {
+            self.pos += len;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_segments(&mut self) -> Result<Vec<Segment>, ServiceError> {
+        let mut segments = Vec::new();
+        loop {
+            let recursive = self.eat_str("..");
+            let selector = if recursive {
+                match self.peek() {
+                    Some('[') => self.parse_bracket()?,
+                    _ => self.parse_dot_selector()?,
+                }
+            } else if self.eat('.') {
+                self.parse_dot_selector()?
+            } else if self.peek() == Some('[') {
+                self.parse_bracket()?
+            } else {
+                return Ok(segments);
+            };
+            segments.push(Segment {
+                recursive,
+                selector,
+            });
+        }
+    }
+
+    fn parse_dot_selector(&mut self) -> Result<Selector, ServiceError> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let name = self.parse_name();
+        if name.is_empty() {
+            return Err(self.error("expected a member name"));
+        }
+        Ok(Selector::Name(name))
+    }
+
+    fn parse_name(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '$')
+        {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn parse_bracket(&mut self) -> Result<Selector, ServiceError> {
+        self.eat('[');
+        self.skip_spaces();
+        let selector = if self.eat('?') {
+            self.skip_spaces();
+            // Os parênteses em volta do filtro são opcionais
+            let parenthesized = self.eat('(');
+            let filter = self.parse_or()?;
+            self.skip_spaces();
+            if parenthesized && !self.eat(')') {
+                return Err(self.error("expected ')'"));
+            }
+            Selector::Filter(filter)
+        } else {
+            let mut selectors = vec![self.parse_bracket_item()?];
+            self.skip_spaces();
+            while self.eat(',') {
+                self.skip_spaces();
+                selectors.push(self.parse_bracket_item()?);
+                self.skip_spaces();
+            }
+            if selectors.len() == 1 {
+                selectors.remove(0)
+            } else {
+                Selector::Union(selectors)
+            }
+        };
+        self.skip_spaces();
+        if !self.eat(']') {
+            return Err(self.error("expected ']'"));
+        }
+        Ok(selector)
+    }
+
+    fn parse_bracket_item(&mut self) -> Result<Selector, ServiceError> {
+        match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some(quote @ ('\'' | '"')) => Ok(Selector::Name(self.parse_string(quote)?)),
+            _ => {
+                let start = self.parse_integer()?;
+                self.skip_spaces();
+                if !self.eat(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("expected an index, name or '*'"));
+                }
+                let end = self.parse_integer()?;
+                let step = if self.eat(':') {
+                    self.parse_integer()?.unwrap_or(1)
+                } else {
+                    1
+                };
+                if step == 0 {
+                    return Err(self.error("slice step cannot be zero"));
+                }
+                Ok(Selector::Slice(start, end, step))
+            }
+        }
+    }
+
+    fn parse_integer(&mut self) -> Result<Option<i64>, ServiceError> {
+        self.skip_spaces();
+        let start = self.pos;
+        self.eat('-');
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Ok(None);
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse()
+            .map(Some)
+            .map_err(|_| self.error("invalid integer"))
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<String, ServiceError> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(c) => out.push(c),
+                        None => return Err(self.error("unterminated string")),
+                    }
+                    self.pos += 1;
+                }
+                Some(c) => {
+                    out.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, ServiceError> {
+        let mut left = self.parse_and()?;
+        loop {
+            self.skip_spaces();
+            if !self.eat_str("||") {
+                return Ok(left);
+            }
+            let right = self.parse_and()?;
+            left = FilterExpr::Or(Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, ServiceError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            self.skip_spaces();
+            if !self.eat_str("&&") {
+                return Ok(left);
+            }
+            let right = self.parse_unary()?;
+            left = FilterExpr::And(Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, ServiceError> {
+        self.skip_spaces();
+        if self.peek() == Some('!') && self.chars.get(self.pos + 1) != Some(&'=') {
+            self.pos += 1;
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat('(') {
+            let inner = self.parse_or()?;
+            self.skip_spaces();
+            if !self.eat(')') {
+                return Err(self.error("expected ')'"));
+            }
+            return Ok(inner);
+        }
+        let left = self.parse_operand()?;
+        self.skip_spaces();
+        let op = if self.eat_str("==") {
+            CompareOp::Eq
+        } else if self.eat_str("!=") {
+            CompareOp::Ne
+        } else if self.eat_str("<=") {
+            CompareOp::Le
+        } else if self.eat_str(">=") {
+            CompareOp::Ge
+        } else if self.eat('<') {
+            CompareOp::Lt
+        } else if self.eat('>') {
+            CompareOp::Gt
+        } else {
+            return Ok(FilterExpr::Exists(left));
+        };
+        let right = self.parse_operand()?;
+        Ok(FilterExpr::Compare(left, op, right))
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, ServiceError> {
+        self.skip_spaces();
+        match self.peek() {
+            Some(c @ ('@' | '$')) => {
+                self.pos += 1;
+                Ok(Operand::Path {
+                    root: c == '$',
+                    segments: self.parse_segments()?,
+                })
+            }
+            Some(quote @ ('\'' | '"')) => {
+                Ok(Operand::Literal(Value::String(self.parse_string(quote)?)))
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' => {
+                let start = self.pos;
+                self.pos += 1;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+                {
+                    self.pos += 1;
+                }
+                let text: String = self.chars[start..self.pos].iter().collect();
+                serde_json::from_str::<Value>(&text)
+                    .ok()
+                    .filter(Value::is_number)
+                    .map(Operand::Literal)
+                    .ok_or_else(|| self.error("invalid number"))
+            }
+            _ => {
+                let word = self.parse_name();
+                match word.as_str() {
+                    "true" => Ok(Operand::Literal(Value::Bool(true))),
+                    "false" => Ok(Operand::Literal(Value::Bool(false))),
+                    "null" => Ok(Operand::Literal(Value::Null)),
+                    _ => Err(self.error("expected '@', '$' or a literal")),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1023 @@
+// Validador de JSON Schema (draft 2020-12 e draft-07) sobre o `Value`
+// intermediário do conversor, então o schema e o documento podem estar em
+// qualquer formato de entrada. `$ref` resolve referências locais (ponteiros,
+// `$anchor` e `$id` do próprio schema); schemas remotos não são baixados.
+// `format` é verificado nos formatos conhecidos e ignorado nos demais.
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::enums::schema_draft::SchemaDraft;
+use crate::services::error::ServiceError;
+use crate::services::json_patch::json_equal;
+use crate::structs::schema_violation::SchemaViolation;
+
+// Limite de `$ref` seguidos sem descer no documento; só um schema recursivo que
+// nunca consome o documento chega a ele
+const MAX_DEPTH: usize = 128;
+
+/// Todas as violações do documento, na ordem em que as palavras-chave são
+/// avaliadas. Um schema inválido (ex.: `$ref` sem destino ou `pattern` que não
+/// compila) é um erro, não uma violação.
+pub fn validate(schema: &Value, instance: &Value) -> Result<Vec<SchemaViolation>, ServiceError> {
+    let mut validator = Validator {
+        draft: SchemaDraft::from_schema(schema),
+        resources: HashMap::new(),
+        bases: HashMap::new(),
+        regexes: RefCell::new(HashMap::new()),
+    };
+    let root_base = schema
+        .get("$id")
+        .and_then(Value::as_str)
+        .map(|id| strip_fragment(id).to_string())
+        .unwrap_or_default();
+    validator.resources.insert(root_base.clone(), schema);
+    validator.index(schema, &root_base);
+
+    let mut violations = Vec::new();
+    validator.validate(schema, instance, "", "", &mut violations, 0)?;
+    Ok(violations)
+}
+
+/// Uma violação por bloco: caminho no documento, palavra-chave e mensagem, e o
+/// caminho no schema na linha seguinte.
+pub fn format_violations(violations: &[SchemaViolation]) -> String {
+    violations
+        .iter()
+        .map(|v| {
+            let instance_path = if v.instance_path.is_empty() {
+                "(root)"
+            } else {
+                &v.instance_path
+            };
+            format!(
+                "{} [{}] {}\n    schema: #{}",
+                instance_path, v.keyword, v.message, v.schema_path
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Propriedades e índices avaliados com sucesso, usados por
+/// `unevaluatedProperties` e `unevaluatedItems`.
+#[derive(Default)]
+struct Evaluated {
+    properties: BTreeSet<String>,
+    items: BTreeSet<usize>,
+}
+
+impl Evaluated {
+    fn merge(&mut self, other: Evaluated) {
+        self.properties.extend(other.properties);
+        self.items.extend(other.items);
+    }
+}
+
+struct Validator<'a> {
+    draft: SchemaDraft,
+    /// Schemas por URI (`$id`) e por âncora (`uri#nome`).
+    resources: HashMap<String, &'a Value>,
+    /// URI base de cada subschema, pelo endereço do `Value`.
+    bases: HashMap<*const Value, String>,
+    regexes: RefCell<HashMap<String, Regex>>,
+}
+
+impl<'a> Validator<'a> {
+    fn index(&mut self, schema: &'a Value, base: &str) {
+        let map = match schema {
+            Value::Object(map) => map,
+            Value::Array(items) => {
+                for item in items {
+                    self.index(item, base);
+                }
+                return;
+            }
+            _ => return,
+        };
+        let mut base = base.to_string();
+        if let Some(id) = map.get("$id").and_then(Value::as_str) {
+            if id.starts_with('#') {
+                // No draft-07, `$id: "#nome"` é uma âncora
+                self.resources.insert(join_uri(&base, id), schema);
+            } else {
+                base = strip_fragment(&join_uri(&base, id)).to_string();
+                self.resources.insert(base.clone(), schema);
+            }
+        }
+        for keyword in ["$anchor", "$dynamicAnchor"] {
+            if let Some(anchor) = map.get(keyword).and_then(Value::as_str) {
+                self.resources
+                    .insert(format!("{}#{}", base, anchor), schema);
+            }
+        }
+        self.bases.insert(schema as *const Value, base.clone());
+        for (key, value) in map {
+            if !matches!(key.as_str(), "enum" | "const" | "examples" | "default") {
+                self.index(value, &base);
+            }
+        }
+    }
+
+    fn resolve(&self, schema: &Value, reference: &str) -> Result<&'a Value, ServiceError> {
+        let base = self
+            .bases
+            .get(&(schema as *const Value))
+            .map(String::as_str)
+            .unwrap_or_default();
+        let full = join_uri(base, reference);
+        let target = match full.split_once('#') {
+            Some((uri, fragment)) if fragment.starts_with('/') => self
+                .resources
+                .get(uri)
+                .and_then(|doc| doc.pointer(&percent_decode(fragment))),
+            Some((uri, "")) => self.resources.get(uri).copied(),
+            _ => self.resources.get(&full).copied(),
+        };
+        target.ok_or_else(|| {
+            ServiceError::validation(format!("cannot resolve $ref '{}' in the schema", reference))
+        })
+    }
+
+    fn regex(&self, pattern: &str) -> Result<Regex, ServiceError> {
+        if let Some(regex) = self.regexes.borrow().get(pattern) {
+            return Ok(regex.clone());
+        }
+        let regex = Regex::new(pattern).map_err(|e| {
+            ServiceError::validation(format!(
+                "invalid pattern '{}' in the schema: {}",
+                pattern, e
+            ))
+        })?;
+        self.regexes
+            .borrow_mut()
+            .insert(pattern.to_string(), regex.clone());
+        Ok(regex)
+    }
+
+    // Valida sem registrar violações; usado por anyOf, oneOf, not, if e contains
+    fn check(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        depth: usize,
+    ) -> Result<(bool, Evaluated), ServiceError> {
+        let mut violations = Vec::new();
+        let evaluated = self.validate(
+            schema,
+            instance,
+            instance_path,
+            schema_path,
+            &mut violations,
+            depth,
+        )?;
+        Ok((violations.is_empty(), evaluated))
+    }
+
+    fn validate(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        out: &mut Vec<SchemaViolation>,
+        depth: usize,
+    ) -> Result<Evaluated, ServiceError> {
+        let map = match schema {
+            Value::Bool(true) => return Ok(Evaluated::default()),
+            Value::Bool(false) => {
+                out.push(violation(
+                    instance_path,
+                    schema_path,
+                    "false",
+                    "no value is allowed here".to_string(),
+                ));
+                return Ok(Evaluated::default());
+            }
+            Value::Object(map) => map,
+            _ => {
+                return Err(ServiceError::validation(format!(
+                    "invalid schema at '#{}': expected an object or a boolean",
+                    schema_path
+                )));
+            }
+        };
+        if depth > MAX_DEPTH {
+            return Err(ServiceError::validation(
+                "the schema references itself without end ($ref)",
+            ));
+        }
+
+        let mut evaluated = Evaluated::default();
+        let push = |out: &mut Vec<SchemaViolation>, keyword: &str, message: String| {
+            out.push(violation(
+                instance_path,
+                &child_path(schema_path, keyword),
+                keyword,
+                message,
+            ));
+        };
+
+        for keyword in ["$ref", "$dynamicRef"] {
+            if let Some(reference) = map.get(keyword).and_then(Value::as_str) {
+                let target = self.resolve(schema, reference)?;
+                let path = child_path(schema_path, keyword);
+                evaluated.merge(self.validate(
+                    target,
+                    instance,
+                    instance_path,
+                    &path,
+                    out,
+                    depth + 1,
+                )?);
+                // No draft-07, as palavras-chave ao lado de `$ref` são ignoradas
+                if self.draft == SchemaDraft::Draft7 {
+                    return Ok(evaluated);
+                }
+            }
+        }
+
+        if let Some(types) = map.get("type") {
+            let allowed: Vec<&str> = match types {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(list) => list.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|t| type_matches(t, instance)) {
+                push(
+                    out,
+                    "type",
+                    format!(
+                        "expected {}, found {}",
+                        allowed.join(" or "),
+                        type_name(instance)
+                    ),
+                );
+            }
+        }
+        if let Some(Value::Array(options)) = map.get("enum")
+            && !options.iter().any(|o| json_equal(o, instance))
+        {
+            push(
+                out,
+                "enum",
+                "value is not one of the allowed values".to_string(),
+            );
+        }
+        if let Some(expected) = map.get("const")
+            && !json_equal(expected, instance)
+        {
+            push(out, "const", format!("value must be {}", expected));
+        }
+
+        match instance {
+            Value::Number(n) => self.validate_number(map, n.as_f64().unwrap_or(0.0), out, &push),
+            Value::String(s) => self.validate_string(map, s, out, &push)?,
+            Value::Array(items) => {
+                self.validate_array(map, items, instance_path, schema_path, out, &mut evaluated)?
+            }
+            Value::Object(object) => self.validate_object(
+                map,
+                object,
+                instance_path,
+                schema_path,
+                out,
+                depth,
+                &mut evaluated,
+            )?,
+            _ => {}
+        }
+
+        if let Some(Value::Array(schemas)) = map.get("allOf") {
+            for (i, sub) in schemas.iter().enumerate() {
+                let path = child_path(&child_path(schema_path, "allOf"), &i.to_string());
+                evaluated.merge(self.validate(sub, instance, instance_path, &path, out, depth)?);
+            }
+        }
+        if let Some(Value::Array(schemas)) = map.get("anyOf") {
+            let mut any = false;
+            for (i, sub) in schemas.iter().enumerate() {
+                let path = child_path(&child_path(schema_path, "anyOf"), &i.to_string());
+                let (valid, sub_evaluated) =
+                    self.check(sub, instance, instance_path, &path, depth)?;
+                if valid {
+                    any = true;
+                    evaluated.merge(sub_evaluated);
+                }
+            }
+            if !any {
+                push(
+                    out,
+                    "anyOf",
+                    "value does not match any schema in anyOf".to_string(),
+                );
+            }
+        }
+        if let Some(Value::Array(schemas)) = map.get("oneOf") {
+            let mut matches = 0;
+            for (i, sub) in schemas.iter().enumerate() {
+                let path = child_path(&child_path(schema_path, "oneOf"), &i.to_string());
+                let (valid, sub_evaluated) =
+                    self.check(sub, instance, instance_path, &path, depth)?;
+                if valid {
+                    matches += 1;
+                    evaluated.merge(sub_evaluated);
+                }
+            }
+            if matches != 1 {
+                push(
+                    out,
+                    "oneOf",
+                    format!(
+                        "value matches {} schemas in oneOf, expected exactly one",
+                        matches
+                    ),
+                );
+            }
+        }
+        if let Some(sub) = map.get("not") {
+            let path = child_path(schema_path, "not");
+            if self.check(sub, instance, instance_path, &path, depth)?.0 {
+                push(
+                    out,
+                    "not",
+                    "value must not match the schema in not".to_string(),
+                );
+            }
+        }
+        if let Some(condition) = map.get("if") {
+            let path = child_path(schema_path, "if");
+            let (valid, if_evaluated) =
+                self.check(condition, instance, instance_path, &path, depth)?;
+            let branch = if valid { "then" } else { "else" };
+            if valid {
+                evaluated.merge(if_evaluated);
+            }
+            if let Some(sub) = map.get(branch) {
+                let path = child_path(schema_path, branch);
+                evaluated.merge(self.validate(sub, instance, instance_path, &path, out, depth)?);
+            }
+        }
+
+        // Por último, pois dependem do que as demais palavras-chave avaliaram
+        if let Some(sub) = map.get("unevaluatedProperties")
+            && let Value::Object(object) = instance
+        {
+            let path = child_path(schema_path, "unevaluatedProperties");
+            for (key, value) in object {
+                if !evaluated.properties.contains(key) {
+                    let item_path = child_path(instance_path, key);
+                    self.validate_additional(
+                        sub,
+                        value,
+                        &item_path,
+                        &path,
+                        "unevaluatedProperties",
+                        key,
+                        out,
+                    )?;
+                }
+            }
+            evaluated.properties.extend(object.keys().cloned());
+        }
+        if let Some(sub) = map.get("unevaluatedItems")
+            && let Value::Array(items) = instance
+        {
+            let path = child_path(schema_path, "unevaluatedItems");
+            for (i, item) in items.iter().enumerate() {
+                if !evaluated.items.contains(&i) {
+                    let item_path = child_path(instance_path, &i.to_string());
+                    self.validate(sub, item, &item_path, &path, out, 0)?;
+                }
+            }
+            evaluated.items.extend(0..items.len());
+        }
+
+        Ok(evaluated)
+    }
+
+    fn validate_number(
+        &self,
+        map: &Map<String, Value>,
+        n: f64,
+        out: &mut Vec<SchemaViolation>,
+        push: &impl Fn(&mut Vec<SchemaViolation>, &str, String),
+    ) {
+        let limit = |keyword: &str| map.get(keyword).and_then(Value::as_f64);
+        if let Some(m) = limit("multipleOf")
+            && m > 0.0
+        {
+            let quotient = n / m;
+            if (quotient - quotient.round()).abs() > 1e-9 {
+                push(
+                    out,
+                    "multipleOf",
+                    format!("{} is not a multiple of {}", n, m),
+                );
+            }
+        }
+        if let Some(max) = limit("maximum")
+            && n > max
+        {
+            push(
+                out,
+                "maximum",
+                format!("{} is greater than the maximum of {}", n, max),
+            );
+        }
+        if let Some(max) = limit("exclusiveMaximum")
+            && n >= max
+        {
+            push(
+                out,
+                "exclusiveMaximum",
+                format!("{} must be less than {}", n, max),
+            );
+        }
+        if let Some(min) = limit("minimum")
+            && n < min
+        {
+            push(
+                out,
+                "minimum",
+                format!("{} is less than the minimum of {}", n, min),
+            );
+        }
+        if let Some(min) = limit("exclusiveMinimum")
+            && n <= min
+        {
+            push(
+                out,
+                "exclusiveMinimum",
+                format!("{} must be greater than {}", n, min),
+            );
+        }
+    }
+
+    fn validate_string(
+        &self,
+        map: &Map<String, Value>,
+        s: &str,
+        out: &mut Vec<SchemaViolation>,
+        push: &impl Fn(&mut Vec<SchemaViolation>, &str, String),
+    ) -> Result<(), ServiceError> {
+        let length = s.chars().count() as u64;
+        if let Some(max) = map.get("maxLength").and_then(Value::as_u64)
+            && length > max
+        {
+            push(
+                out,
+                "maxLength",
+                format!("string is longer than {} characters", max),
+            );
+        }
+        if let Some(min) = map.get("minLength").and_then(Value::as_u64)
+            && length < min
+        {
+            push(
+                out,
+                "minLength",
+                format!("string is shorter than {} characters", min),
+            );
+        }
+        if let Some(pattern) = map.get("pattern").and_then(Value::as_str)
+            && !self.regex(pattern)?.is_match(s)
+        {
+            push(
+                out,
+                "pattern",
+                format!("string does not match the pattern '{}'", pattern),
+            );
+        }
+        if let Some(format) = map.get("format").and_then(Value::as_str)
+            && check_format(format, s) == Some(false)
+        {
+            push(out, "format", format!("string is not a valid {}", format));
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn validate_array(
+        &self,
+        map: &Map<String, Value>,
+        items: &[Value],
+        instance_path: &str,
+        schema_path: &str,
+        out: &mut Vec<SchemaViolation>,
+        evaluated: &mut Evaluated,
+    ) -> Result<(), ServiceError> {
+        let item_path = |i: usize| child_path(instance_path, &i.to_string());
+
+        // Itens posicionais: `prefixItems` na 2020-12, `items` em forma de array no draft-07
+        let (tuple_keyword, rest_keyword) = match self.draft {
+            SchemaDraft::Draft202012 => ("prefixItems", "items"),
+            SchemaDraft::Draft7 => ("items", "additionalItems"),
+        };
+        let mut rest_start = 0;
+        if let Some(Value::Array(schemas)) = map.get(tuple_keyword) {
+            for (i, (sub, item)) in schemas.iter().zip(items).enumerate() {
+                let path = child_path(&child_path(schema_path, tuple_keyword), &i.to_string());
+                self.validate(sub, item, &item_path(i), &path, out, 0)?;
+                evaluated.items.insert(i);
+            }
+            rest_start = schemas.len();
+        }
+        // No draft-07, `additionalItems` só vale quando `items` é um array
+        let rest = match (self.draft, map.get(tuple_keyword), map.get(rest_keyword)) {
+            (SchemaDraft::Draft7, Some(sub @ (Value::Object(_) | Value::Bool(_))), _) => {
+                Some(("items", sub))
+            }
+            (SchemaDraft::Draft7, Some(Value::Array(_)), Some(sub)) => Some((rest_keyword, sub)),
+            (SchemaDraft::Draft7, _, _) => None,
+            (SchemaDraft::Draft202012, _, sub) => sub.map(|s| (rest_keyword, s)),
+        };
+        if let Some((keyword, sub)) = rest {
+            let path = child_path(schema_path, keyword);
+            for (i, item) in items.iter().enumerate().skip(rest_start) {
+                if sub == &Value::Bool(false) {
+                    out.push(violation(
+                        &item_path(i),
+                        &path,
+                        keyword,
+                        format!("array must not have more than {} items", rest_start),
+                    ));
+                    break;
+                }
+                self.validate(sub, item, &item_path(i), &path, out, 0)?;
+                evaluated.items.insert(i);
+            }
+        }
+
+        if let Some(sub) = map.get("contains") {
+            let path = child_path(schema_path, "contains");
+            let mut matched = 0;
+            for (i, item) in items.iter().enumerate() {
+                if self.check(sub, item, &item_path(i), &path, 0)?.0 {
+                    matched += 1;
+                    evaluated.items.insert(i);
+                }
+            }
+            let min = map.get("minContains").and_then(Value::as_u64).unwrap_or(1);
+            if (matched as u64) < min {
+                out.push(violation(
+                    instance_path,
+                    &path,
+                    "contains",
+                    format!(
+                        "array must contain at least {} matching items, found {}",
+                        min, matched
+                    ),
+                ));
+            }
+            if let Some(max) = map.get("maxContains").and_then(Value::as_u64)
+                && matched as u64 > max
+            {
+                out.push(violation(
+                    instance_path,
+                    &child_path(schema_path, "maxContains"),
+                    "maxContains",
+                    format!(
+                        "array must contain at most {} matching items, found {}",
+                        max, matched
+                    ),
+                ));
+            }
+        }
+
+        let push = |out: &mut Vec<SchemaViolation>, keyword: &str, message: String| {
+            out.push(violation(
+                instance_path,
+                &child_path(schema_path, keyword),
+                keyword,
+                message,
+            ));
+        };
+        let len = items.len() as u64;
+        if let Some(max) = map.get("maxItems").and_then(Value::as_u64)
+            && len > max
+        {
+            push(
+                out,
+                "maxItems",
+                format!("array has more than {} items", max),
+            );
+        }
+        if let Some(min) = map.get("minItems").and_then(Value::as_u64)
+            && len < min
+        {
+            push(
+                out,
+                "minItems",
+                format!("array has fewer than {} items", min),
+            );
+        }
+        if map.get("uniqueItems") == Some(&Value::Bool(true)) {
+            let duplicate = (0..items.len())
+                .flat_map(|i| (i + 1..items.len()).map(move |j| (i, j)))
+                .find(|&(i, j)| json_equal(&items[i], &items[j]));
+            if let Some((i, j)) = duplicate {
+                push(
+                    out,
+                    "uniqueItems",
+                    format!("items {} and {} are equal", i, j),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn validate_object(
+        &self,
+        map: &Map<String, Value>,
+        object: &Map<String, Value>,
+        instance_path: &str,
+        schema_path: &str,
+        out: &mut Vec<SchemaViolation>,
+        depth: usize,
+        evaluated: &mut Evaluated,
+    ) -> Result<(), ServiceError> {
+        let push = |out: &mut Vec<SchemaViolation>, keyword: &str, message: String| {
+            out.push(violation(
+                instance_path,
+                &child_path(schema_path, keyword),
+                keyword,
+                message,
+            ));
+        };
+
+        let properties = match map.get("properties") {
+            Some(Value::Object(properties)) => Some(properties),
+            _ => None,
+        };
+        if let Some(properties) = properties {
+            for (key, sub) in properties {
+                if let Some(value) = object.get(key) {
+                    let path = child_path(&child_path(schema_path, "properties"), key);
+                    self.validate(sub, value, &child_path(instance_path, key), &path, out, 0)?;
+                    evaluated.properties.insert(key.clone());
+                }
+            }
+        }
+
+        let mut patterns = Vec::new();
+        if let Some(Value::Object(pattern_properties)) = map.get("patternProperties") {
+            for (pattern, sub) in pattern_properties {
+                let regex = self.regex(pattern)?;
+                let path = child_path(&child_path(schema_path, "patternProperties"), pattern);
+                for (key, value) in object {
+                    if regex.is_match(key) {
+                        self.validate(sub, value, &child_path(instance_path, key), &path, out, 0)?;
+                        evaluated.properties.insert(key.clone());
+                    }
+                }
+                patterns.push(regex);
+            }
+        }
+
+        if let Some(sub) = map.get("additionalProperties") {
+            let path = child_path(schema_path, "additionalProperties");
+            for (key, value) in object {
+                let declared = properties.is_some_and(|p| p.contains_key(key))
+                    || patterns.iter().any(|r| r.is_match(key));
+                if !declared {
+                    let item_path = child_path(instance_path, key);
+                    self.validate_additional(
+                        sub,
+                        value,
+                        &item_path,
+                        &path,
+                        "additionalProperties",
+                        key,
+                        out,
+                    )?;
+                    evaluated.properties.insert(key.clone());
+                }
+            }
+        }
+
+        if let Some(sub) = map.get("propertyNames") {
+            let path = child_path(schema_path, "propertyNames");
+            for key in object.keys() {
+                let name = Value::String(key.clone());
+                self.validate(sub, &name, &child_path(instance_path, key), &path, out, 0)?;
+            }
+        }
+
+        if let Some(Value::Array(required)) = map.get("required") {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    push(
+                        out,
+                        "required",
+                        format!("missing required property '{}'", key),
+                    );
+                }
+            }
+        }
+
+        // `dependencies` do draft-07 reúne os dois casos: lista de nomes ou schema
+        for keyword in ["dependentRequired", "dependentSchemas", "dependencies"] {
+            let Some(Value::Object(dependencies)) = map.get(keyword) else {
+                continue;
+            };
+            for (key, dependency) in dependencies {
+                if !object.contains_key(key) {
+                    continue;
+                }
+                let path = child_path(&child_path(schema_path, keyword), key);
+                match dependency {
+                    Value::Array(names) => {
+                        for name in names.iter().filter_map(Value::as_str) {
+                            if !object.contains_key(name) {
+                                out.push(violation(
+                                    instance_path,
+                                    &path,
+                                    keyword,
+                                    format!("property '{}' requires property '{}'", key, name),
+                                ));
+                            }
+                        }
+                    }
+                    sub => {
+                        let instance = Value::Object(object.clone());
+                        evaluated.merge(self.validate(
+                            sub,
+                            &instance,
+                            instance_path,
+                            &path,
+                            out,
+                            depth,
+                        )?);
+                    }
+                }
+            }
+        }
+
+        let len = object.len() as u64;
+        if let Some(max) = map.get("maxProperties").and_then(Value::as_u64)
+            && len > max
+        {
+            push(
+                out,
+                "maxProperties",
+                format!("object has more than {} properties", max),
+            );
+        }
+        if let Some(min) = map.get("minProperties").and_then(Value::as_u64)
+            && len < min
+        {
+            push(
+                out,
+                "minProperties",
+                format!("object has fewer than {} properties", min),
+            );
+        }
+        Ok(())
+    }
+
+    // `additionalProperties` e `unevaluatedProperties`: com `false`, a mensagem
+    // cita a propriedade em vez do genérico "no value is allowed"
+    #[allow(clippy::too_many_arguments)]
+    fn validate_additional(
+        &self,
+        schema: &Value,
+        value: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        keyword: &str,
+        key: &str,
+        out: &mut Vec<SchemaViolation>,
+    ) -> Result<(), ServiceError> {
+        if schema == &Value::Bool(false) {
+            out.push(violation(
+                instance_path,
+                schema_path,
+                keyword,
+                format!("property '{}' is not allowed", key),
+            ));
+            return Ok(());
+        }
+        self.validate(schema, value, instance_path, schema_path, out, 0)
+            .map(|_| ())
+    }
+}
+
+fn violation(
+    instance_path: &str,
+    schema_path: &str,
+    keyword: &str,
+    message: String,
+) -> SchemaViolation {
+    SchemaViolation {
+        instance_path: instance_path.to_string(),
+        schema_path: schema_path.to_string(),
+        keyword: keyword.to_string(),
+        message,
+    }
+}
+
+fn child_path(path: &str, token: &str) -> String {
+    format!("{}/{}", path, token.replace('~', "~0").replace('/', "~1"))
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "number" => value.is_number(),
+        // 1.0 também é inteiro desde o draft-06
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn strip_fragment(uri: &str) -> &str {
+    uri.split_once('#').map_or(uri, |(base, _)| base)
+}
+
+// Resolução simplificada de URI relativa (RFC 3986) suficiente para `$id` e `$ref`
+fn join_uri(base: &str, reference: &str) -> String {
+    let base = strip_fragment(base);
+    if reference.contains("://") || reference.starts_with("urn:") || base.is_empty() {
+        return reference.to_string();
+    }
+    if reference.starts_with('#') {
+        return format!("{}{}", base, reference);
+    }
+    if reference.starts_with('/') {
+        let origin_end = base
+            .find("://")
+            .and_then(|i| base[i + 3..].find('/').map(|j| i + 3 + j))
+            .unwrap_or(base.len());
+        return format!("{}{}", &base[..origin_end], reference);
+    }
+    match base.rfind('/') {
+        Some(i) => format!("{}{}", &base[..=i], reference),
+        None => reference.to_string(),
+    }
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = text
+                .get(i + 1..i + 3)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Verifica os formatos conhecidos; `None` para formatos não suportados.
+pub(crate) fn check_format(format: &str, s: &str) -> Option<bool> {
+    Some(match format {
+        "date-time" => s
+            .split_once(['T', 't'])
+            .is_some_and(|(date, time)| is_date(date) && is_time(time)),
+        "date" => is_date(s),
+        "time" => is_time(s),
+        "email" => is_email(s),
+        "uuid" => is_uuid(s),
+        "ipv4" => s.parse::<Ipv4Addr>().is_ok(),
+        "ipv6" => s.parse::<Ipv6Addr>().is_ok(),
+        "hostname" => is_hostname(s),
+        "uri" => is_uri(s),
+        "regex" => Regex::new(s).is_ok(),
+        _ => return None,
+    })
+}
+
+fn digits(s: &str, count: usize) -> Option<u32> {
+    (s.len() == count && s.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| s.parse().ok())
+        .flatten()
+}
+
+// `AAAA-MM-DD` com dia válido para o mês (incluindo anos bissextos)
+pub(crate) fn is_date(s: &str) -> bool {
+    let mut parts = s.split('-');
+    let (Some(y), Some(m), Some(d), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    let (Some(year), Some(month), Some(day)) = (digits(y, 4), digits(m, 2), digits(d, 2)) else {
+        return false;
+    };
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+// `HH:MM:SS`, fração opcional e fuso obrigatório (`Z` ou `±HH:MM`), como no RFC 3339
+fn is_time(s: &str) -> bool {
+    let (clock, offset) = if let Some(clock) = s.strip_suffix(['Z', 'z']) {
+        (clock, None)
+    } else {
+        match s.rfind(['+', '-']) {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => return false,
+        }
+    };
+    let clock = clock.split_once('.').map_or(Some(clock), |(c, fraction)| {
+        (!fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit())).then_some(c)
+    });
+    let Some(clock) = clock else {
+        return false;
+    };
+    let valid_clock = match clock.split(':').collect::<Vec<_>>()[..] {
+        [h, m, sec] => matches!(
+            (digits(h, 2), digits(m, 2), digits(sec, 2)),
+            (Some(h), Some(m), Some(sec)) if h < 24 && m < 60 && sec <= 60
+        ),
+        _ => false,
+    };
+    let valid_offset = offset.is_none_or(|o| match o.split_once(':') {
+        Some((h, m)) => {
+            matches!((digits(h, 2), digits(m, 2)), (Some(h), Some(m)) if h < 24 && m < 60)
+        }
+        None => false,
+    });
+    valid_clock && valid_offset
+}
+
+pub(crate) fn is_email(s: &str) -> bool {
+    let Some((local, domain)) = s.rsplit_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !local.contains(|c: char| c.is_whitespace() || c == '@')
+        && domain.contains('.')
+        && is_hostname(domain)
+}
+
+pub(crate) fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+fn is_hostname(s: &str) -> bool {
+    !s.is_empty()
+        && s.len() <= 253
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+}
+
+pub(crate) fn is_uri(s: &str) -> bool {
+    let Some((scheme, rest)) = s.split_once(':') else {
+        return false;
+    };
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !rest.is_empty()
+        && !s.contains(char::is_whitespace)
+}
@@ -1,5 +1,21 @@
 pub mod csv_converter;
+pub mod error;
+pub mod file_hash;
+pub mod format;
+pub mod format_detector;
 pub mod hdoc_request;
+pub mod json5_converter;
 pub mod json_converter;
+pub mod json_diff;
+pub mod json_patch;
+pub mod json_path;
+pub mod json_schema;
+pub mod ndjson_converter;
+pub mod rust_generator;
+pub mod schema_inference;
+pub mod toml_converter;
+pub mod type_generator;
+pub mod typescript_generator;
 pub mod xml_converter;
+pub mod xpath;
 pub mod yaml_converter;
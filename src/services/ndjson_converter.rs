@@ -0,0 +1,87 @@
+use serde_json::Value;
+
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::ServiceError;
+use crate::services::format::Format;
+use crate::services::{csv_converter, json_converter, xml_converter, yaml_converter};
+use crate::structs::converter_options::ConverterOptions;
+use crate::structs::csv_options::CsvOptions;
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+
+/// JSON Lines / NDJSON: um valor JSON por linha.
+pub struct NdjsonFormat;
+
+impl Format for NdjsonFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::Ndjson
+    }
+
+    fn name(&self) -> &'static str {
+        "NDJSON"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ndjson", "jsonl"]
+    }
+
+    fn parse(&self, input: &str, _options: &ConverterOptions) -> Result<Value, ServiceError> {
+        ndjson_to_value(input)
+    }
+
+    fn emit(&self, value: &Value, _options: &ConverterOptions) -> Result<String, ServiceError> {
+        value_to_ndjson(value)
+    }
+}
+
+/// Lê cada linha não vazia como um item do array; erros apontam a linha do arquivo.
+pub fn ndjson_to_value(ndjson_str: &str) -> Result<Value, ServiceError> {
+    let mut out = Vec::new();
+    for (index, line) in ndjson_str.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value = serde_json::from_str(line)
+            .map_err(|e| json_converter::json_syntax_error_at("NDJSON", ndjson_str, index, e))?;
+        out.push(value);
+    }
+    Ok(Value::Array(out))
+}
+
+/// Arrays viram uma linha compacta por item; outros valores, uma linha só.
+pub fn value_to_ndjson(value: &Value) -> Result<String, ServiceError> {
+    let items = match value {
+        Value::Array(arr) => arr.as_slice(),
+        _ => std::slice::from_ref(value),
+    };
+    let mut out = String::new();
+    for item in items {
+        let line = serde_json::to_string(item).map_err(|e| ServiceError::serialize("NDJSON", e))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+pub fn ndjson_to_json(ndjson_str: &str) -> Result<String, ServiceError> {
+    ndjson_to_value(ndjson_str).and_then(|v| json_converter::value_to_json(&v))
+}
+
+pub fn ndjson_to_csv(ndjson_str: &str) -> Result<String, ServiceError> {
+    ndjson_to_value(ndjson_str)
+        .and_then(|v| csv_converter::value_to_csv(&v, &CsvOptions::default()))
+}
+
+pub fn ndjson_to_yaml(ndjson_str: &str) -> Result<String, ServiceError> {
+    ndjson_to_value(ndjson_str)
+        .and_then(|v| yaml_converter::value_to_yaml(&v, &YamlOptions::default()))
+}
+
+pub fn ndjson_to_xml(ndjson_str: &str) -> Result<String, ServiceError> {
+    ndjson_to_value(ndjson_str)
+        .and_then(|v| xml_converter::value_to_xml(&v, &XmlOptions::default()))
+}
+
+pub fn pretty_ndjson(ndjson_str: &str) -> Result<String, ServiceError> {
+    ndjson_to_value(ndjson_str).and_then(|v| value_to_ndjson(&v))
+}
@@ -0,0 +1,173 @@
+// Geração de structs Rust (serde) a partir de um exemplo. Os tipos vêm de
+// `type_generator`, sobre o schema inferido, então objetos de um mesmo array são
+// mesclados em uma única struct e campos ausentes em parte deles viram `Option`.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::ServiceError;
+use crate::services::format::Format;
+use crate::services::type_generator::{self, Field, Language, ROOT_NAME, words};
+use crate::structs::converter_options::ConverterOptions;
+
+// Palavras reservadas que não podem ser nome de campo
+const KEYWORDS: [&str; 53] = [
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "gen", "union",
+];
+
+// Tipos do prelúdio e nomes usados pelo código gerado; uma struct com esses
+// nomes esconderia o tipo original (ex.: `pub struct String`)
+const RESERVED_TYPES: [&str; 24] = [
+    "String",
+    "Vec",
+    "Option",
+    "Some",
+    "None",
+    "Box",
+    "Result",
+    "Ok",
+    "Err",
+    "Self",
+    "Serialize",
+    "Deserialize",
+    "Debug",
+    "Clone",
+    "Copy",
+    "Default",
+    "Send",
+    "Sync",
+    "Sized",
+    "Drop",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "Iterator",
+];
+
+/// Saída que descreve a entrada como structs Rust com `Serialize`/`Deserialize`.
+pub struct RustFormat;
+
+impl Format for RustFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::Rust
+    }
+
+    fn name(&self) -> &'static str {
+        "Rust"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["rs"]
+    }
+
+    fn can_parse(&self) -> bool {
+        false
+    }
+
+    fn parse(&self, _input: &str, _options: &ConverterOptions) -> Result<Value, ServiceError> {
+        Err(ServiceError::validation(
+            "Rust can only be used as an output format",
+        ))
+    }
+
+    fn emit(&self, value: &Value, _options: &ConverterOptions) -> Result<String, ServiceError> {
+        Ok(value_to_rust(value))
+    }
+}
+
+/// Uma struct por objeto, a raiz primeiro (`Root`). Arrays viram `Vec<T>`,
+/// chaves que não são identificadores válidos ganham `#[serde(rename)]` e tipos
+/// misturados no mesmo campo viram `serde_json::Value`. Um documento que não é
+/// objeto vira um alias (ex.: `pub type Root = Vec<RootItem>;`).
+pub fn value_to_rust(value: &Value) -> String {
+    let (root_type, structs) = type_generator::generate::<RustFormat>(value);
+
+    let mut blocks = Vec::new();
+    if !structs.is_empty() {
+        blocks.push("use serde::{Deserialize, Serialize};\n".to_string());
+    }
+    if root_type != ROOT_NAME {
+        blocks.push(format!("pub type {} = {};\n", ROOT_NAME, root_type));
+    }
+    blocks.extend(structs);
+    blocks.join("\n")
+}
+
+impl Language for RustFormat {
+    const ANY: &'static str = "serde_json::Value";
+    const MAP: &'static str = "serde_json::Map<String, serde_json::Value>";
+    const RESERVED: &'static [&'static str] = &RESERVED_TYPES;
+    const UNION_SEPARATOR: Option<&'static str> = None;
+
+    fn scalar(kind: &str) -> &'static str {
+        match kind {
+            "string" => "String",
+            "integer" => "i64",
+            "number" => "f64",
+            "boolean" => "bool",
+            // `null` sozinho: qualquer valor
+            _ => Self::ANY,
+        }
+    }
+
+    fn array(item: &str) -> String {
+        format!("Vec<{}>", item)
+    }
+
+    fn nullable(inner: &str) -> String {
+        if inner == Self::ANY {
+            inner.to_string()
+        } else {
+            format!("Option<{}>", inner)
+        }
+    }
+
+    fn declaration(name: &str, fields: &[Field]) -> String {
+        let mut definition = format!(
+            "#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct {} {{\n",
+            name
+        );
+        let mut names = HashSet::new();
+        for field in fields {
+            let base = field_name(field.key);
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while !names.insert(name.clone()) {
+                name = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            let field_type = if field.required || field.type_name.starts_with("Option<") {
+                field.type_name.clone()
+            } else {
+                format!("Option<{}>", field.type_name)
+            };
+            if name != field.key {
+                definition.push_str(&format!("    #[serde(rename = {:?})]\n", field.key));
+            }
+            definition.push_str(&format!("    pub {}: {},\n", name, field_type));
+        }
+        definition.push_str("}\n");
+        definition
+    }
+}
+
+fn field_name(key: &str) -> String {
+    let name = words(key)
+        .iter()
+        .map(|w| w.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("field_{}", name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
@@ -0,0 +1,253 @@
+// Geração de JSON Schema (draft 2020-12) a partir de exemplos. Um array no
+// topo (inclusive o CSV lido como linhas e fluxos com vários documentos) é a
+// lista de exemplos; qualquer outro valor é um exemplo único.
+
+use serde_json::{Map, Value, json};
+
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::ServiceError;
+use crate::services::format::Format;
+use crate::services::json_converter;
+use crate::services::json_schema::check_format;
+use crate::structs::converter_options::ConverterOptions;
+
+/// Limite de valores distintos para um texto virar `enum`.
+const MAX_ENUM_VALUES: usize = 5;
+
+// Verificados na ordem; o primeiro que todos os textos satisfazem vira `format`
+const FORMATS: [&str; 4] = ["date-time", "date", "email", "uuid"];
+
+/// Saída que descreve a entrada como JSON Schema, escrito em JSON.
+pub struct JsonSchemaFormat;
+
+impl Format for JsonSchemaFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::JsonSchema
+    }
+
+    fn name(&self) -> &'static str {
+        "JSON Schema"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["json"]
+    }
+
+    fn can_parse(&self) -> bool {
+        false
+    }
+
+    fn parse(&self, _input: &str, _options: &ConverterOptions) -> Result<Value, ServiceError> {
+        Err(ServiceError::validation(
+            "JSON Schema can only be used as an output format",
+        ))
+    }
+
+    fn emit(&self, value: &Value, _options: &ConverterOptions) -> Result<String, ServiceError> {
+        json_converter::value_to_json(&infer_schema(value))
+    }
+}
+
+/// Schema que aceita todos os exemplos. Propriedades presentes em todos os
+/// objetos são `required`; textos que se repetem entre poucos valores viram
+/// `enum`; tipos diferentes no mesmo lugar viram uma lista em `type` ou, quando
+/// têm restrições próprias, variantes em `anyOf`.
+pub fn infer_schema(samples: &Value) -> Value {
+    let inferred = match samples {
+        Value::Array(items) => infer(items),
+        other => infer(std::slice::from_ref(other)),
+    };
+    let mut schema = Map::new();
+    schema.insert(
+        "$schema".to_string(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    if let Value::Object(inferred) = inferred {
+        schema.extend(inferred);
+    }
+    Value::Object(schema)
+}
+
+/// Schema sem `$schema` que aceita todos os exemplos; também é a base dos
+/// geradores de código.
+pub(crate) fn infer(samples: &[Value]) -> Value {
+    let mut shape = Shape::default();
+    samples.iter().for_each(|sample| shape.add(sample));
+    shape.to_schema()
+}
+
+/// Tudo o que foi visto em uma posição dos exemplos, separado por tipo.
+#[derive(Default)]
+struct Shape {
+    null: bool,
+    boolean: bool,
+    integer: bool,
+    number: bool,
+    string: Option<StringShape>,
+    /// Itens de todos os arrays vistos, mesclados.
+    array: Option<Box<Shape>>,
+    object: Option<ObjectShape>,
+}
+
+struct StringShape {
+    count: usize,
+    /// Valores distintos; `None` quando passam de `MAX_ENUM_VALUES`.
+    values: Option<Vec<String>>,
+    /// Formatos que todos os textos vistos satisfazem.
+    formats: Vec<&'static str>,
+}
+
+#[derive(Default)]
+struct ObjectShape {
+    count: usize,
+    /// Na ordem em que aparecem, com quantos objetos têm a propriedade.
+    properties: Vec<(String, usize, Shape)>,
+}
+
+impl Shape {
+    fn is_empty(&self) -> bool {
+        !self.null
+            && !self.boolean
+            && !self.integer
+            && !self.number
+            && self.string.is_none()
+            && self.array.is_none()
+            && self.object.is_none()
+    }
+
+    fn add(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.null = true,
+            Value::Bool(_) => self.boolean = true,
+            Value::Number(n) if n.is_i64() || n.is_u64() => self.integer = true,
+            Value::Number(_) => self.number = true,
+            Value::String(s) => self.add_string(s),
+            Value::Array(items) => {
+                let shape = self.array.get_or_insert_default();
+                items.iter().for_each(|item| shape.add(item));
+            }
+            Value::Object(map) => {
+                let shape = self.object.get_or_insert_default();
+                shape.count += 1;
+                for (key, item) in map {
+                    match shape.properties.iter_mut().find(|(k, _, _)| k == key) {
+                        Some((_, present, property)) => {
+                            *present += 1;
+                            property.add(item);
+                        }
+                        None => {
+                            let mut property = Shape::default();
+                            property.add(item);
+                            shape.properties.push((key.clone(), 1, property));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_string(&mut self, s: &str) {
+        let shape = self.string.get_or_insert_with(|| StringShape {
+            count: 0,
+            values: Some(Vec::new()),
+            formats: FORMATS.to_vec(),
+        });
+        shape.count += 1;
+        shape.formats.retain(|f| check_format(f, s) == Some(true));
+        if let Some(values) = &mut shape.values
+            && !values.iter().any(|v| v == s)
+        {
+            if values.len() < MAX_ENUM_VALUES {
+                values.push(s.to_string());
+            } else {
+                shape.values = None;
+            }
+        }
+    }
+
+    fn to_schema(&self) -> Value {
+        // Uma variante por tipo, de estruturas para escalares
+        let mut variants: Vec<Map<String, Value>> = Vec::new();
+        if let Some(object) = &self.object {
+            variants.push(object.to_schema());
+        }
+        if let Some(items) = &self.array {
+            let mut schema = typed("array");
+            if !items.is_empty() {
+                schema.insert("items".to_string(), items.to_schema());
+            }
+            variants.push(schema);
+        }
+        if let Some(string) = &self.string {
+            variants.push(string.to_schema());
+        }
+        // Inteiros misturados com decimais são só `number`
+        if self.number {
+            variants.push(typed("number"));
+        } else if self.integer {
+            variants.push(typed("integer"));
+        }
+        if self.boolean {
+            variants.push(typed("boolean"));
+        }
+        if self.null {
+            variants.push(typed("null"));
+        }
+
+        if variants.len() == 1 {
+            return Value::Object(variants.remove(0));
+        }
+        if variants.is_empty() {
+            return Value::Object(Map::new());
+        }
+        if variants.iter().all(|v| v.len() == 1) {
+            let types = variants.iter().map(|v| v["type"].clone()).collect();
+            return Value::Object(Map::from_iter([("type".to_string(), Value::Array(types))]));
+        }
+        json!({ "anyOf": variants })
+    }
+}
+
+impl StringShape {
+    fn to_schema(&self) -> Map<String, Value> {
+        let mut schema = typed("string");
+        if let Some(format) = self.formats.first() {
+            schema.insert("format".to_string(), json!(format));
+        } else if let Some(values) = &self.values
+            && self.count > values.len()
+        {
+            // Só com repetição; um valor visto uma vez não indica um conjunto fechado
+            schema.insert("enum".to_string(), json!(values));
+        }
+        schema
+    }
+}
+
+impl ObjectShape {
+    fn to_schema(&self) -> Map<String, Value> {
+        let mut schema = typed("object");
+        if self.properties.is_empty() {
+            return schema;
+        }
+        let properties = self
+            .properties
+            .iter()
+            .map(|(key, _, shape)| (key.clone(), shape.to_schema()))
+            .collect();
+        schema.insert("properties".to_string(), Value::Object(properties));
+        let required: Vec<Value> = self
+            .properties
+            .iter()
+            .filter(|(_, present, _)| *present == self.count)
+            .map(|(key, _, _)| json!(key))
+            .collect();
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::Array(required));
+        }
+        schema
+    }
+}
+
+fn typed(name: &str) -> Map<String, Value> {
+    Map::from_iter([("type".to_string(), json!(name))])
+}
@@ -0,0 +1,161 @@
+use serde_json::{Map, Value};
+
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::{ServiceError, SourceLocation};
+use crate::services::format::Format;
+use crate::services::{csv_converter, json_converter, xml_converter, yaml_converter};
+use crate::structs::converter_options::ConverterOptions;
+use crate::structs::csv_options::CsvOptions;
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+
+pub struct TomlFormat;
+
+impl Format for TomlFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::Toml
+    }
+
+    fn name(&self) -> &'static str {
+        "TOML"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["toml"]
+    }
+
+    fn parse(&self, input: &str, _options: &ConverterOptions) -> Result<Value, ServiceError> {
+        toml_to_value(input)
+    }
+
+    fn emit(&self, value: &Value, _options: &ConverterOptions) -> Result<String, ServiceError> {
+        value_to_toml(value)
+    }
+}
+
+pub fn toml_to_value(toml_str: &str) -> Result<Value, ServiceError> {
+    let table: toml::Table = toml::from_str(toml_str).map_err(|e| match e.span() {
+        Some(span) => {
+            let location = SourceLocation::from_offset(toml_str, span.start);
+            ServiceError::syntax("TOML", e.message().to_string(), e, location)
+        }
+        None => ServiceError::parse("TOML", e),
+    })?;
+    Ok(toml_to_json_value(&toml::Value::Table(table)))
+}
+
+fn toml_to_json_value(toml: &toml::Value) -> Value {
+    match toml {
+        toml::Value::String(s) => Value::String(s.clone()),
+        toml::Value::Integer(i) => Value::Number((*i).into()),
+        toml::Value::Float(f) => serde_json::Number::from_f64(*f)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        toml::Value::Boolean(b) => Value::Bool(*b),
+        // JSON não tem tipo de data, então mantém a representação textual do TOML
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(arr) => Value::Array(arr.iter().map(toml_to_json_value).collect()),
+        toml::Value::Table(t) => {
+            let mut map = Map::new();
+            for (k, v) in t {
+                map.insert(k.clone(), toml_to_json_value(v));
+            }
+            Value::Object(map)
+        }
+    }
+}
+
+pub fn value_to_toml(value: &Value) -> Result<String, ServiceError> {
+    let table = match json_to_toml_value(value, "$")? {
+        toml::Value::Table(t) => t,
+        _ => {
+            return Err(ServiceError::unsupported_shape(
+                "TOML",
+                "the document root must be an object (arrays and plain values are not supported)",
+            ));
+        }
+    };
+    toml::to_string_pretty(&table).map_err(|e| ServiceError::serialize("TOML", e))
+}
+
+fn json_to_toml_value(value: &Value, path: &str) -> Result<toml::Value, ServiceError> {
+    match value {
+        Value::Null => Err(ServiceError::unsupported_shape(
+            "TOML",
+            format!("null values are not supported (found null at '{}')", path),
+        )),
+        Value::Bool(b) => Ok(toml::Value::Boolean(*b)),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Ok(toml::Value::Integer(i))
+            } else if n.is_u64() {
+                Err(ServiceError::unsupported_shape(
+                    "TOML",
+                    format!(
+                        "number {} at '{}' exceeds the 64-bit integer range",
+                        n, path
+                    ),
+                ))
+            } else {
+                Ok(toml::Value::Float(n.as_f64().unwrap_or_default()))
+            }
+        }
+        Value::String(s) => Ok(toml::Value::String(s.clone())),
+        Value::Array(arr) => {
+            let mut out = Vec::with_capacity(arr.len());
+            let mut first_type: Option<&'static str> = None;
+            for (i, v) in arr.iter().enumerate() {
+                let item_path = format!("{}[{}]", path, i);
+                let item = json_to_toml_value(v, &item_path)?;
+                let item_type = item.type_str();
+                match first_type {
+                    None => first_type = Some(item_type),
+                    Some(t) if t != item_type => {
+                        return Err(ServiceError::unsupported_shape(
+                            "TOML",
+                            format!(
+                                "mixed-type arrays are not supported ('{}' contains {} and {})",
+                                path, t, item_type
+                            ),
+                        ));
+                    }
+                    _ => {}
+                }
+                out.push(item);
+            }
+            Ok(toml::Value::Array(out))
+        }
+        Value::Object(map) => {
+            let mut table = toml::Table::new();
+            for (k, v) in map {
+                let item_path = format!("{}.{}", path, k);
+                table.insert(k.clone(), json_to_toml_value(v, &item_path)?);
+            }
+            Ok(toml::Value::Table(table))
+        }
+    }
+}
+
+pub fn toml_to_json(toml_str: &str) -> Result<String, ServiceError> {
+    toml_to_value(toml_str).and_then(|v| json_converter::value_to_json(&v))
+}
+
+pub fn json_to_toml(json_str: &str) -> Result<String, ServiceError> {
+    json_converter::json_to_value(json_str).and_then(|v| value_to_toml(&v))
+}
+
+pub fn pretty_toml(toml_str: &str) -> Result<String, ServiceError> {
+    toml_to_value(toml_str).and_then(|v| value_to_toml(&v))
+}
+
+pub fn toml_to_csv(toml_str: &str) -> Result<String, ServiceError> {
+    toml_to_value(toml_str).and_then(|v| csv_converter::value_to_csv(&v, &CsvOptions::default()))
+}
+
+pub fn toml_to_yaml(toml_str: &str) -> Result<String, ServiceError> {
+    toml_to_value(toml_str).and_then(|v| yaml_converter::value_to_yaml(&v, &YamlOptions::default()))
+}
+
+pub fn toml_to_xml(toml_str: &str) -> Result<String, ServiceError> {
+    toml_to_value(toml_str).and_then(|v| xml_converter::value_to_xml(&v, &XmlOptions::default()))
+}
@@ -0,0 +1,218 @@
+// Base dos geradores de código (Rust, TypeScript). Percorre o schema inferido
+// em `schema_inference`, dá um nome único a cada tipo de objeto e deixa a
+// sintaxe para a `Language` de destino.
+
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use serde_json::{Map, Value};
+
+use crate::services::schema_inference;
+
+/// Nome do tipo gerado para o documento inteiro.
+pub(crate) const ROOT_NAME: &str = "Root";
+
+/// Sintaxe de uma linguagem de destino.
+pub(crate) trait Language {
+    /// Tipo que aceita qualquer valor.
+    const ANY: &'static str;
+    /// Objeto sem propriedades conhecidas.
+    const MAP: &'static str;
+    /// Nomes de tipos que uma declaração gerada esconderia.
+    const RESERVED: &'static [&'static str];
+    /// Separador de uniões; sem ele, tipos misturados viram `ANY`.
+    const UNION_SEPARATOR: Option<&'static str>;
+
+    /// Tipo de um escalar do schema (`string`, `integer`, `number`, `boolean`, `null`).
+    fn scalar(kind: &str) -> &'static str;
+    fn array(item: &str) -> String;
+    fn nullable(inner: &str) -> String;
+    /// Declaração de um tipo de objeto com os campos na ordem da entrada.
+    fn declaration(name: &str, fields: &[Field]) -> String;
+}
+
+/// Propriedade de um objeto já com o tipo resolvido.
+pub(crate) struct Field<'a> {
+    pub key: &'a str,
+    pub type_name: String,
+    /// Presente em todos os exemplos.
+    pub required: bool,
+}
+
+/// Tipo da raiz e as declarações, na ordem em que os objetos aparecem (a raiz
+/// primeiro). Quando a raiz não é objeto, o tipo é um alias e `ROOT_NAME` fica
+/// reservado para ele.
+pub(crate) fn generate<L: Language>(value: &Value) -> (String, Vec<String>) {
+    let schema = schema_inference::infer(std::slice::from_ref(value));
+    let mut generator = Generator::<L> {
+        declarations: Vec::new(),
+        names: L::RESERVED.iter().map(|n| n.to_string()).collect(),
+        language: PhantomData,
+    };
+    if !value.is_object() {
+        generator.names.insert(ROOT_NAME.to_string());
+    }
+    let root_type = generator.type_for(&schema, ROOT_NAME);
+    (root_type, generator.declarations)
+}
+
+struct Generator<L> {
+    declarations: Vec<String>,
+    names: HashSet<String>,
+    language: PhantomData<L>,
+}
+
+impl<L: Language> Generator<L> {
+    fn type_for(&mut self, schema: &Value, name_hint: &str) -> String {
+        let Value::Object(schema) = schema else {
+            return L::ANY.to_string();
+        };
+        let variants: Vec<Value> = match (schema.get("anyOf"), schema.get("type")) {
+            (Some(Value::Array(variants)), _) => variants.clone(),
+            (_, Some(Value::Array(types))) => types
+                .iter()
+                .map(|t| Value::Object(Map::from_iter([("type".to_string(), t.clone())])))
+                .collect(),
+            _ => return self.single_type(schema, name_hint),
+        };
+        let nullable = variants.iter().any(|v| v["type"] == "null");
+        let rest: Vec<&Value> = variants.iter().filter(|v| v["type"] != "null").collect();
+        match (rest.as_slice(), L::UNION_SEPARATOR) {
+            ([only], _) => {
+                let inner = self.type_for(only, name_hint);
+                if nullable { L::nullable(&inner) } else { inner }
+            }
+            (_, Some(separator)) => {
+                let members: Vec<String> = variants
+                    .iter()
+                    .map(|v| self.type_for(v, name_hint))
+                    .collect();
+                members.join(separator)
+            }
+            _ => L::ANY.to_string(),
+        }
+    }
+
+    fn single_type(&mut self, schema: &Map<String, Value>, name_hint: &str) -> String {
+        match schema.get("type").and_then(Value::as_str) {
+            Some("array") => match schema.get("items") {
+                Some(items) => L::array(&self.type_for(items, &singular(name_hint))),
+                None => L::array(L::ANY),
+            },
+            Some("object") => match schema.get("properties") {
+                Some(Value::Object(properties)) => {
+                    let required = schema.get("required").and_then(Value::as_array);
+                    self.declaration_for(properties, required, name_hint)
+                }
+                _ => L::MAP.to_string(),
+            },
+            Some(kind) => L::scalar(kind).to_string(),
+            // Schema vazio (array sempre vazio): qualquer valor
+            None => L::ANY.to_string(),
+        }
+    }
+
+    fn declaration_for(
+        &mut self,
+        properties: &Map<String, Value>,
+        required: Option<&Vec<Value>>,
+        name_hint: &str,
+    ) -> String {
+        let name = self.unique_name(&pascal_case(name_hint));
+        // Reserva a posição para a declaração sair antes das que ela usa
+        let slot = self.declarations.len();
+        self.declarations.push(String::new());
+
+        let fields: Vec<Field> = properties
+            .iter()
+            .map(|(key, property)| Field {
+                key,
+                type_name: self.type_for(property, key),
+                required: required.is_some_and(|r| r.iter().any(|k| k == key)),
+            })
+            .collect();
+        self.declarations[slot] = L::declaration(&name, &fields);
+        name
+    }
+
+    fn unique_name(&mut self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut suffix = 2;
+        while !self.names.insert(unique.clone()) {
+            unique = format!("{}{}", name, suffix);
+            suffix += 1;
+        }
+        unique
+    }
+}
+
+/// Palavras de uma chave: separa em caracteres que não são letras ou dígitos
+/// ASCII e nas trocas de caixa (`userId`, `HTTPServer`).
+pub(crate) fn words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if !prev.is_ascii_uppercase() || next_lower {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// `PascalCase` para nomes de tipos; nunca começa com dígito.
+fn pascal_case(key: &str) -> String {
+    let name: String = words(key)
+        .iter()
+        .map(|w| {
+            let lower = w.to_ascii_lowercase();
+            let mut chars = lower.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    match name.chars().next() {
+        None => "Item".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("T{}", name),
+        Some(_) => name,
+    }
+}
+
+/// Nome do item de um array: `users` → `user`, `categories` → `category`,
+/// `addresses` → `address` e, quando não há plural, `data` → `dataItem`.
+fn singular(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies")
+        && !stem.is_empty()
+    {
+        format!("{}y", stem)
+    } else if let Some(stem) = name.strip_suffix("es")
+        && ["ss", "sh", "ch", "x"]
+            .iter()
+            .any(|end| stem.ends_with(end))
+    {
+        stem.to_string()
+    } else if let Some(stem) = name.strip_suffix('s')
+        && !stem.is_empty()
+        && !stem.ends_with('s')
+    {
+        stem.to_string()
+    } else {
+        format!("{}Item", name)
+    }
+}
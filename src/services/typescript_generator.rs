@@ -0,0 +1,120 @@
+// Geração de declarações TypeScript a partir de um exemplo, com a mesma
+// inferência dos structs Rust: objetos de um mesmo array viram uma única
+// interface e propriedades ausentes em parte deles ficam opcionais (`?`).
+
+use serde_json::Value;
+
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::ServiceError;
+use crate::services::format::Format;
+use crate::services::type_generator::{self, Field, Language, ROOT_NAME};
+use crate::structs::converter_options::ConverterOptions;
+
+// Tipos globais que uma interface gerada esconderia (ex.: `interface Date`)
+const RESERVED_TYPES: [&str; 16] = [
+    "Array", "Boolean", "Date", "Error", "Function", "Map", "Number", "Object", "Omit", "Partial",
+    "Promise", "Readonly", "Record", "Required", "Set", "String",
+];
+
+/// Saída que descreve a entrada como `interface`/`type` do TypeScript.
+pub struct TypeScriptFormat;
+
+impl Format for TypeScriptFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::TypeScript
+    }
+
+    fn name(&self) -> &'static str {
+        "TypeScript"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ts"]
+    }
+
+    fn can_parse(&self) -> bool {
+        false
+    }
+
+    fn parse(&self, _input: &str, _options: &ConverterOptions) -> Result<Value, ServiceError> {
+        Err(ServiceError::validation(
+            "TypeScript can only be used as an output format",
+        ))
+    }
+
+    fn emit(&self, value: &Value, _options: &ConverterOptions) -> Result<String, ServiceError> {
+        Ok(value_to_typescript(value))
+    }
+}
+
+/// Uma interface por objeto, a raiz primeiro (`Root`). Tipos diferentes no
+/// mesmo lugar viram uniões (ex.: `(Item | string)[]`) e chaves que não são
+/// identificadores ficam entre aspas. Um documento que não é objeto vira um
+/// alias (ex.: `export type Root = RootItem[];`).
+pub fn value_to_typescript(value: &Value) -> String {
+    let (root_type, interfaces) = type_generator::generate::<TypeScriptFormat>(value);
+
+    let mut blocks = Vec::new();
+    if root_type != ROOT_NAME {
+        blocks.push(format!("export type {} = {};\n", ROOT_NAME, root_type));
+    }
+    blocks.extend(interfaces);
+    blocks.join("\n")
+}
+
+impl Language for TypeScriptFormat {
+    const ANY: &'static str = "unknown";
+    const MAP: &'static str = "Record<string, unknown>";
+    const RESERVED: &'static [&'static str] = &RESERVED_TYPES;
+    const UNION_SEPARATOR: Option<&'static str> = Some(" | ");
+
+    fn scalar(kind: &str) -> &'static str {
+        match kind {
+            "string" => "string",
+            "integer" | "number" => "number",
+            "boolean" => "boolean",
+            "null" => "null",
+            _ => Self::ANY,
+        }
+    }
+
+    fn array(item: &str) -> String {
+        // Uniões precisam de parênteses antes de `[]`
+        if item.contains(" | ") {
+            format!("({})[]", item)
+        } else {
+            format!("{}[]", item)
+        }
+    }
+
+    fn nullable(inner: &str) -> String {
+        format!("{} | null", inner)
+    }
+
+    fn declaration(name: &str, fields: &[Field]) -> String {
+        let mut declaration = format!("export interface {} {{\n", name);
+        for field in fields {
+            declaration.push_str(&format!(
+                "  {}{}: {};\n",
+                property_name(field.key),
+                if field.required { "" } else { "?" },
+                field.type_name
+            ));
+        }
+        declaration.push_str("}\n");
+        declaration
+    }
+}
+
+// Identificadores ficam como estão; as demais chaves viram string JSON
+fn property_name(key: &str) -> String {
+    let identifier = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
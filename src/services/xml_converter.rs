@@ -1,82 +1,413 @@
-use quick_xml::Reader;
-use quick_xml::events::Event;
-use serde::Serialize;
+use std::io::Cursor;
+
+use quick_xml::events::{BytesEnd, BytesRef, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
 use serde_json::{Map, Value};
 
-use crate::services::json_converter;
+use crate::enums::format_converter::FormatConverter;
+use crate::enums::xml_content_mode::XmlContentMode;
+use crate::services::error::{ServiceError, SourceLocation};
+use crate::services::format::Format;
+use crate::services::{
+    csv_converter, json_converter, ndjson_converter, toml_converter, yaml_converter,
+};
+use crate::structs::converter_options::ConverterOptions;
+use crate::structs::csv_options::CsvOptions;
+use crate::structs::xml_dom::{DomNode, DomNodeKind, XmlDom};
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+
+pub struct XmlFormat;
+
+impl Format for XmlFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::Xml
+    }
+
+    fn name(&self) -> &'static str {
+        "XML"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["xml"]
+    }
+
+    fn parse(&self, input: &str, options: &ConverterOptions) -> Result<Value, ServiceError> {
+        xml_to_value(input, &options.xml)
+    }
+
+    fn emit(&self, value: &Value, options: &ConverterOptions) -> Result<String, ServiceError> {
+        value_to_xml(value, &options.xml)
+    }
+}
+
+// Conteúdo de um elemento na ordem do documento
+enum XmlNode {
+    Text(String),
+    Element(String, Value),
+}
+
+// Elemento aberto durante a leitura: atributos e conteúdo já lidos
+struct XmlFrame {
+    tag: String,
+    attributes: Map<String, Value>,
+    content: Vec<XmlNode>,
+}
+
+impl XmlFrame {
+    // Texto, CDATA e referências consecutivos formam um único trecho
+    fn push_text(&mut self, text: &str) {
+        match self.content.last_mut() {
+            Some(XmlNode::Text(t)) => t.push_str(text),
+            _ => self.content.push(XmlNode::Text(text.to_string())),
+        }
+    }
+}
 
-pub fn xml_to_json(xml_str: &str) -> Result<String, String> {
+/// Lê o prefixo das chaves de atributos. Vazio não é aceito: o atributo `id`
+/// e um elemento filho `<id>` virariam a mesma chave.
+pub fn parse_attribute_prefix(value: &str) -> Result<String, ServiceError> {
+    if value.is_empty() {
+        return Err(ServiceError::validation(
+            "XML attribute prefix cannot be empty",
+        ));
+    }
+    Ok(value.to_string())
+}
+
+pub fn xml_to_value(xml_str: &str, options: &XmlOptions) -> Result<Value, ServiceError> {
+    parse_attribute_prefix(&options.attribute_prefix)?;
     let mut reader = Reader::from_str(xml_str);
-    reader.config_mut().trim_text(true);
-    let mut buf = Vec::new();
-    let mut stack: Vec<(String, Value)> = Vec::new();
-    let mut current_text = String::new();
+    let mut stack: Vec<XmlFrame> = Vec::new();
+    let mut root: Option<(String, Value)> = None;
+
+    let syntax_error = |reader: &Reader<&[u8]>, e: quick_xml::Error| {
+        let location = SourceLocation::from_offset(xml_str, reader.error_position() as usize);
+        ServiceError::parse_at("XML", e, location)
+    };
 
     loop {
-        match reader.read_event_into(&mut buf) {
+        match reader.read_event() {
             Ok(Event::Start(e)) => {
-                let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
-                stack.push((tag, Value::Object(Map::new())));
+                let frame = open_element(&e, options).map_err(|er| syntax_error(&reader, er))?;
+                stack.push(frame);
+            }
+            Ok(Event::Empty(e)) => {
+                let frame = open_element(&e, options).map_err(|er| syntax_error(&reader, er))?;
+                close_element(frame, &mut stack, &mut root, options);
+            }
+            Ok(Event::End(_)) => {
+                if let Some(frame) = stack.pop() {
+                    close_element(frame, &mut stack, &mut root, options);
+                }
             }
             Ok(Event::Text(e)) => {
-                let cow = reader
-                    .decoder()
-                    .decode(e.as_ref())
-                    .map_err(|er| format!("Erro ao decodificar texto: {}", er))?;
-                current_text = cow.trim().to_string();
-            }
-            Ok(Event::End(e)) => {
-                let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
-                let (elem_tag, mut elem_val) = stack
-                    .pop()
-                    .unwrap_or_else(|| (tag.clone(), Value::Object(Map::new())));
-                if !current_text.is_empty() {
-                    if let Value::Object(ref mut map) = elem_val {
-                        map.insert("_text".to_string(), parse_text_value(&current_text));
-                    }
-                    current_text.clear();
+                if let Some(frame) = stack.last_mut() {
+                    frame.push_text(&e.xml_content().map_err(ServiceError::encoding)?);
                 }
-                // Se o elemento contém apenas texto, colapsa para string pura
-                if let Value::Object(ref mut map) = elem_val {
-                    if map.len() == 1 {
-                        if let Some(v) = map.remove("_text") {
-                            elem_val = v;
-                        }
-                    }
+            }
+            Ok(Event::CData(e)) => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.push_text(&e.xml_content().map_err(ServiceError::encoding)?);
                 }
-                if let Some((_, Value::Object(parent))) = stack.last_mut() {
-                    if let Some(existing) = parent.get_mut(&elem_tag) {
-                        match existing {
-                            Value::Array(arr) => arr.push(elem_val),
-                            v => *v = Value::Array(vec![v.clone(), elem_val]),
-                        }
-                    } else {
-                        parent.insert(elem_tag, elem_val);
-                    }
-                } else {
-                    // root
-                    stack.push((elem_tag, elem_val));
+            }
+            Ok(Event::GeneralRef(e)) => {
+                if let Some(frame) = stack.last_mut() {
+                    let resolved = resolve_reference(&e).map_err(|er| syntax_error(&reader, er))?;
+                    frame.push_text(&resolved);
                 }
             }
             Ok(Event::Eof) => break,
-            Err(e) => return Err(format!("Erro ao ler XML: {}", e)),
+            Err(e) => return Err(syntax_error(&reader, e)),
             _ => (),
         }
-        buf.clear();
-    }
-    if let Some((_, root)) = stack.pop() {
-        let root = normalize_root_array(root);
-        let mut buf = Vec::new();
-        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
-        let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
-        root.serialize(&mut ser)
-            .map_err(|e| format!("Erro ao serializar JSON: {}", e))?;
-        String::from_utf8(buf).map_err(|e| format!("Erro ao converter JSON para UTF-8: {}", e))
+    }
+
+    match root {
+        Some((tag, value)) if options.keep_root => {
+            let mut map = Map::new();
+            map.insert(tag, value);
+            Ok(Value::Object(map))
+        }
+        Some((_, value)) => Ok(normalize_root_array(value)),
+        None => Err(ServiceError::parse("XML", "document has no root element")),
+    }
+}
+
+/// Monta a árvore completa do documento, usada pelas consultas XPath.
+///
+/// Trechos de texto só com espaços entre elementos são descartados.
+pub fn xml_to_dom(xml_str: &str) -> Result<XmlDom, ServiceError> {
+    let mut reader = Reader::from_str(xml_str);
+    let mut dom = XmlDom {
+        nodes: vec![DomNode {
+            kind: DomNodeKind::Document,
+            parent: None,
+            children: Vec::new(),
+        }],
+    };
+    let mut stack = vec![0];
+
+    let syntax_error = |reader: &Reader<&[u8]>, e: quick_xml::Error| {
+        let location = SourceLocation::from_offset(xml_str, reader.error_position() as usize);
+        ServiceError::parse_at("XML", e, location)
+    };
+
+    loop {
+        let parent = *stack.last().unwrap_or(&0);
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let kind = dom_element(&e).map_err(|er| syntax_error(&reader, er))?;
+                stack.push(push_dom_node(&mut dom, parent, kind));
+            }
+            Ok(Event::Empty(e)) => {
+                let kind = dom_element(&e).map_err(|er| syntax_error(&reader, er))?;
+                push_dom_node(&mut dom, parent, kind);
+            }
+            Ok(Event::End(_)) if stack.len() > 1 => {
+                stack.pop();
+            }
+            Ok(Event::Text(e)) => {
+                push_dom_text(
+                    &mut dom,
+                    parent,
+                    &e.xml_content().map_err(ServiceError::encoding)?,
+                );
+            }
+            Ok(Event::CData(e)) => {
+                push_dom_text(
+                    &mut dom,
+                    parent,
+                    &e.xml_content().map_err(ServiceError::encoding)?,
+                );
+            }
+            Ok(Event::GeneralRef(e)) => {
+                let resolved = resolve_reference(&e).map_err(|er| syntax_error(&reader, er))?;
+                push_dom_text(&mut dom, parent, &resolved);
+            }
+            Ok(Event::Comment(e)) => {
+                let text = e.xml_content().map_err(ServiceError::encoding)?;
+                push_dom_node(&mut dom, parent, DomNodeKind::Comment(text.to_string()));
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(syntax_error(&reader, e)),
+            _ => (),
+        }
+    }
+
+    // Remove os trechos só com espaços depois de juntar texto, CDATA e referências,
+    // renumerando os nós para manter os índices na ordem do documento
+    let keep: Vec<bool> = dom
+        .nodes
+        .iter()
+        .map(|n| !matches!(&n.kind, DomNodeKind::Text(t) if t.trim().is_empty()))
+        .collect();
+    let mut new_index = vec![0; keep.len()];
+    let mut next = 0;
+    for (i, kept) in keep.iter().enumerate() {
+        new_index[i] = next;
+        next += usize::from(*kept);
+    }
+    dom.nodes = dom
+        .nodes
+        .into_iter()
+        .zip(&keep)
+        .filter(|(_, kept)| **kept)
+        .map(|(mut node, _)| {
+            node.parent = node.parent.map(|p| new_index[p]);
+            node.children = node
+                .children
+                .into_iter()
+                .filter(|&c| keep[c])
+                .map(|c| new_index[c])
+                .collect();
+            node
+        })
+        .collect();
+
+    if !dom.nodes[0]
+        .children
+        .iter()
+        .any(|&c| matches!(dom.nodes[c].kind, DomNodeKind::Element { .. }))
+    {
+        return Err(ServiceError::parse("XML", "document has no root element"));
+    }
+    Ok(dom)
+}
+
+fn dom_element(e: &BytesStart) -> Result<DomNodeKind, quick_xml::Error> {
+    let mut attributes = Vec::new();
+    for attr in e.attributes() {
+        let attr = attr?;
+        attributes.push((
+            String::from_utf8_lossy(attr.key.as_ref()).to_string(),
+            attr.unescape_value()?.to_string(),
+        ));
+    }
+    Ok(DomNodeKind::Element {
+        name: String::from_utf8_lossy(e.name().as_ref()).to_string(),
+        attributes,
+    })
+}
+
+fn push_dom_node(dom: &mut XmlDom, parent: usize, kind: DomNodeKind) -> usize {
+    let id = dom.nodes.len();
+    dom.nodes.push(DomNode {
+        kind,
+        parent: Some(parent),
+        children: Vec::new(),
+    });
+    dom.nodes[parent].children.push(id);
+    id
+}
+
+// Texto seguido de CDATA ou referência continua no mesmo nó de texto
+fn push_dom_text(dom: &mut XmlDom, parent: usize, text: &str) {
+    if let Some(&last) = dom.nodes[parent].children.last()
+        && let DomNodeKind::Text(existing) = &mut dom.nodes[last].kind
+    {
+        existing.push_str(text);
+        return;
+    }
+    push_dom_node(dom, parent, DomNodeKind::Text(text.to_string()));
+}
+
+fn open_element(e: &BytesStart, options: &XmlOptions) -> Result<XmlFrame, quick_xml::Error> {
+    // O nome qualificado mantém o prefixo de namespace (ex.: "soap:Envelope")
+    let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+    let mut attributes = Map::new();
+    for attr in e.attributes() {
+        let attr = attr?;
+        let key = format!(
+            "{}{}",
+            options.attribute_prefix,
+            String::from_utf8_lossy(attr.key.as_ref())
+        );
+        // Atributos ficam como texto: `id="007"` não pode virar o número 7
+        let value = attr.unescape_value()?;
+        attributes.insert(key, Value::String(value.into_owned()));
+    }
+    Ok(XmlFrame {
+        tag,
+        attributes,
+        content: Vec::new(),
+    })
+}
+
+fn close_element(
+    frame: XmlFrame,
+    stack: &mut [XmlFrame],
+    root: &mut Option<(String, Value)>,
+    options: &XmlOptions,
+) {
+    let tag = frame.tag.clone();
+    let elem_val = frame_to_value(frame, options);
+    if let Some(parent) = stack.last_mut() {
+        parent.content.push(XmlNode::Element(tag, elem_val));
     } else {
-        Err("XML vazio ou inválido".to_string())
+        *root = Some((tag, elem_val));
     }
 }
 
+fn frame_to_value(frame: XmlFrame, options: &XmlOptions) -> Value {
+    let XmlFrame {
+        mut attributes,
+        content,
+        ..
+    } = frame;
+    let has_elements = content.iter().any(|n| matches!(n, XmlNode::Element(..)));
+    let has_text = content
+        .iter()
+        .any(|n| matches!(n, XmlNode::Text(t) if !t.trim().is_empty()));
+    // Espaço na mesma linha entre elementos (`<b>a</b> <i>b</i>`) é conteúdo, não indentação
+    let inline_space = content
+        .iter()
+        .any(|n| matches!(n, XmlNode::Text(t) if !t.is_empty() && !t.contains('\n')));
+
+    if options.content_mode == XmlContentMode::Lossless
+        && has_elements
+        && (has_text || inline_space || !children_grouped(&content))
+    {
+        // Texto misturado com elementos (ou tags intercaladas): guarda tudo em
+        // ordem, inclusive os trechos só com espaços
+        let children = content
+            .into_iter()
+            .map(|n| match n {
+                XmlNode::Text(t) => Value::String(t),
+                XmlNode::Element(tag, v) => {
+                    let mut child = Map::new();
+                    child.insert(tag, v);
+                    Value::Object(child)
+                }
+            })
+            .collect();
+        attributes.insert(options.children_key.clone(), Value::Array(children));
+        return Value::Object(attributes);
+    }
+
+    let mut text_parts = Vec::new();
+    for node in content {
+        match node {
+            XmlNode::Text(t) => {
+                let t = t.trim();
+                if !t.is_empty() {
+                    text_parts.push(t.to_string());
+                }
+            }
+            XmlNode::Element(tag, v) => insert_child(&mut attributes, tag, v),
+        }
+    }
+    if text_parts.is_empty() {
+        return Value::Object(attributes);
+    }
+    let text = parse_text_value(&text_parts.join(" "));
+    // Se o elemento contém apenas texto, colapsa para valor simples
+    if attributes.is_empty() {
+        return text;
+    }
+    attributes.insert(options.text_key.clone(), text);
+    Value::Object(attributes)
+}
+
+// Tags repetidas viram array; só preserva a ordem se as repetições forem consecutivas
+fn children_grouped(content: &[XmlNode]) -> bool {
+    let mut seen: Vec<&str> = Vec::new();
+    for node in content {
+        if let XmlNode::Element(tag, _) = node {
+            if seen.last() == Some(&tag.as_str()) {
+                continue;
+            }
+            if seen.contains(&tag.as_str()) {
+                return false;
+            }
+            seen.push(tag);
+        }
+    }
+    true
+}
+
+fn insert_child(map: &mut Map<String, Value>, tag: String, value: Value) {
+    if let Some(existing) = map.get_mut(&tag) {
+        match existing {
+            Value::Array(arr) => arr.push(value),
+            v => *v = Value::Array(vec![v.take(), value]),
+        }
+    } else {
+        map.insert(tag, value);
+    }
+}
+
+fn resolve_reference(e: &BytesRef) -> Result<String, quick_xml::Error> {
+    if let Some(ch) = e.resolve_char_ref()? {
+        return Ok(ch.to_string());
+    }
+    let name = e.decode()?;
+    // Entidades não predefinidas (declaradas em DTD) são mantidas como no original
+    Ok(quick_xml::escape::resolve_predefined_entity(&name)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("&{};", name)))
+}
+
 fn parse_text_value(s: &str) -> Value {
     if s.eq_ignore_ascii_case("null") {
         return Value::Null;
@@ -90,10 +421,10 @@ fn parse_text_value(s: &str) -> Value {
     if let Ok(i) = s.parse::<i64>() {
         return serde_json::Number::from(i).into();
     }
-    if let Ok(f) = s.parse::<f64>() {
-        if let Some(n) = serde_json::Number::from_f64(f) {
-            return Value::Number(n);
-        }
+    if let Ok(f) = s.parse::<f64>()
+        && let Some(n) = serde_json::Number::from_f64(f)
+    {
+        return Value::Number(n);
     }
     Value::String(s.to_string())
 }
@@ -101,10 +432,10 @@ fn parse_text_value(s: &str) -> Value {
 fn normalize_root_array(v: Value) -> Value {
     match v {
         Value::Object(mut m) => {
-            if m.len() == 1 {
-                if let Some(Value::Array(arr)) = m.remove("item") {
-                    return Value::Array(arr);
-                }
+            if m.len() == 1
+                && let Some(Value::Array(arr)) = m.remove("item")
+            {
+                return Value::Array(arr);
             }
             Value::Object(m)
         }
@@ -112,14 +443,177 @@ fn normalize_root_array(v: Value) -> Value {
     }
 }
 
-pub fn pretty_xml(xml_str: &str) -> Result<String, String> {
-    xml_to_json(xml_str).and_then(|json_str| json_converter::json_to_xml(&json_str))
+pub fn value_to_xml(value: &Value, options: &XmlOptions) -> Result<String, ServiceError> {
+    parse_attribute_prefix(&options.attribute_prefix)?;
+    // Writer com indentação de 4 espaços para saída "bonita"
+    let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 4);
+    // Com keep_root, um objeto de chave única é o próprio elemento raiz
+    let root_element = match value {
+        Value::Object(map) if options.keep_root && map.len() == 1 => map
+            .iter()
+            .next()
+            .filter(|(tag, _)| !is_attribute_key(tag, options) && **tag != options.text_key),
+        _ => None,
+    };
+    match root_element {
+        Some((tag, inner)) => write_value_as_xml(&mut writer, tag, inner, options)?,
+        None => write_value_as_xml(&mut writer, "root", value, options)?,
+    }
+    let result = writer.into_inner().into_inner();
+    String::from_utf8(result).map_err(ServiceError::encoding)
+}
+
+fn is_attribute_key(key: &str, options: &XmlOptions) -> bool {
+    !options.attribute_prefix.is_empty() && key.starts_with(&options.attribute_prefix)
+}
+
+// Arrays dentro de objetos viram elementos repetidos com a mesma tag, o inverso da leitura
+fn write_element<W: std::io::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    value: &Value,
+    options: &XmlOptions,
+) -> Result<(), ServiceError> {
+    match value {
+        Value::Array(arr) => {
+            for v in arr {
+                write_value_as_xml(writer, tag, v, options)?;
+            }
+            Ok(())
+        }
+        _ => write_value_as_xml(writer, tag, value, options),
+    }
+}
+
+fn write_value_as_xml<W: std::io::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    value: &Value,
+    options: &XmlOptions,
+) -> Result<(), ServiceError> {
+    let mut elem = BytesStart::new(tag);
+    let mut text: Option<String> = None;
+    let mut items: &[Value] = &[];
+    let mut children: Vec<(&str, &Value)> = Vec::new();
+    let mut ordered: &[Value] = &[];
+
+    match value {
+        Value::Null => {}
+        Value::Bool(_) | Value::Number(_) | Value::String(_) => text = Some(scalar_to_text(value)),
+        Value::Array(arr) => items = arr,
+        Value::Object(map) => {
+            for (k, v) in map {
+                if *k == options.text_key {
+                    text = Some(scalar_to_text(v));
+                } else if *k == options.children_key
+                    && let Value::Array(arr) = v
+                {
+                    ordered = arr;
+                } else if is_attribute_key(k, options) {
+                    let name = &k[options.attribute_prefix.len()..];
+                    elem.push_attribute((name, scalar_to_text(v).as_str()));
+                } else {
+                    children.push((k, v));
+                }
+            }
+        }
+    }
+
+    if text.is_none() && items.is_empty() && children.is_empty() && ordered.is_empty() {
+        return writer
+            .write_event(Event::Empty(elem))
+            .map_err(|e| ServiceError::serialize("XML", e));
+    }
+
+    writer
+        .write_event(Event::Start(elem))
+        .map_err(|e| ServiceError::serialize("XML", e))?;
+    if ordered.is_empty() {
+        write_content(writer, text, items, children, ordered, options)?;
+    } else {
+        // Conteúdo misto sai sem indentação, que mudaria o texto do elemento
+        let mut inner = Writer::new(Vec::new());
+        write_content(&mut inner, text, items, children, ordered, options)?;
+        let raw = String::from_utf8(inner.into_inner()).map_err(ServiceError::encoding)?;
+        writer
+            .write_event(Event::Text(BytesText::from_escaped(raw)))
+            .map_err(|e| ServiceError::serialize("XML", e))?;
+    }
+    writer
+        .write_event(Event::End(BytesEnd::new(tag)))
+        .map_err(|e| ServiceError::serialize("XML", e))?;
+    Ok(())
+}
+
+fn write_content<W: std::io::Write>(
+    writer: &mut Writer<W>,
+    text: Option<String>,
+    items: &[Value],
+    children: Vec<(&str, &Value)>,
+    ordered: &[Value],
+    options: &XmlOptions,
+) -> Result<(), ServiceError> {
+    if let Some(t) = text {
+        writer
+            .write_event(Event::Text(BytesText::new(&t)))
+            .map_err(|e| ServiceError::serialize("XML", e))?;
+    }
+    for item in items {
+        write_value_as_xml(writer, "item", item, options)?;
+    }
+    for (child_tag, child) in children {
+        write_element(writer, child_tag, child, options)?;
+    }
+    for node in ordered {
+        match node {
+            Value::Object(child) => {
+                for (child_tag, v) in child {
+                    write_element(writer, child_tag, v, options)?;
+                }
+            }
+            other => writer
+                .write_event(Event::Text(BytesText::new(&scalar_to_text(other))))
+                .map_err(|e| ServiceError::serialize("XML", e))?,
+        }
+    }
+    Ok(())
+}
+
+fn scalar_to_text(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub fn xml_to_json(xml_str: &str) -> Result<String, ServiceError> {
+    xml_to_value(xml_str, &XmlOptions::default()).and_then(|v| json_converter::value_to_json(&v))
+}
+
+pub fn pretty_xml(xml_str: &str) -> Result<String, ServiceError> {
+    let options = XmlOptions {
+        keep_root: true,
+        ..XmlOptions::default()
+    };
+    xml_to_value(xml_str, &options).and_then(|v| value_to_xml(&v, &options))
+}
+
+pub fn xml_to_csv(xml_str: &str) -> Result<String, ServiceError> {
+    xml_to_value(xml_str, &XmlOptions::default())
+        .and_then(|v| csv_converter::value_to_csv(&v, &CsvOptions::default()))
+}
+
+pub fn xml_to_yaml(xml_str: &str) -> Result<String, ServiceError> {
+    xml_to_value(xml_str, &XmlOptions::default())
+        .and_then(|v| yaml_converter::value_to_yaml(&v, &YamlOptions::default()))
 }
 
-pub fn xml_to_csv(xml_str: &str) -> Result<String, String> {
-    xml_to_json(xml_str).and_then(|json_str| json_converter::json_to_csv(&json_str))
+pub fn xml_to_toml(xml_str: &str) -> Result<String, ServiceError> {
+    xml_to_value(xml_str, &XmlOptions::default()).and_then(|v| toml_converter::value_to_toml(&v))
 }
 
-pub fn xml_to_yaml(xml_str: &str) -> Result<String, String> {
-    xml_to_json(xml_str).and_then(|json_str| json_converter::json_to_yaml(&json_str))
+pub fn xml_to_ndjson(xml_str: &str) -> Result<String, ServiceError> {
+    xml_to_value(xml_str, &XmlOptions::default())
+        .and_then(|v| ndjson_converter::value_to_ndjson(&v))
 }
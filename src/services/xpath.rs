@@ -0,0 +1,1388 @@
+// Avaliador de XPath 1.0 sobre a árvore montada por `xml_converter::xml_to_dom`:
+// caminhos absolutos e relativos, todos os eixos (com as abreviações `.`, `..`,
+// `@` e `//`), testes `*`, `text()`, `node()` e `comment()`, predicados,
+// operadores, uniões `|` e a biblioteca de funções básica. Variáveis e
+// namespaces não são suportados; nomes com prefixo são comparados como texto.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use quick_xml::Writer;
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+
+use crate::services::error::ServiceError;
+use crate::services::xml_converter;
+use crate::structs::xml_dom::{DomNodeKind, XmlDom};
+use crate::structs::xpath_match::XPathMatch;
+
+// Funções aceitas, com a quantidade mínima e máxima de argumentos
+const FUNCTIONS: [(&str, usize, usize); 27] = [
+    ("last", 0, 0),
+    ("position", 0, 0),
+    ("count", 1, 1),
+    ("name", 0, 1),
+    ("local-name", 0, 1),
+    ("string", 0, 1),
+    ("concat", 2, usize::MAX),
+    ("starts-with", 2, 2),
+    ("contains", 2, 2),
+    ("substring-before", 2, 2),
+    ("substring-after", 2, 2),
+    ("substring", 2, 3),
+    ("string-length", 0, 1),
+    ("normalize-space", 0, 1),
+    ("translate", 3, 3),
+    ("boolean", 1, 1),
+    ("not", 1, 1),
+    ("true", 0, 0),
+    ("false", 0, 0),
+    ("lang", 1, 1),
+    ("number", 0, 1),
+    ("sum", 1, 1),
+    ("floor", 1, 1),
+    ("ceiling", 1, 1),
+    ("round", 1, 1),
+    ("id", 1, 1),
+    ("namespace-uri", 0, 1),
+];
+
+/// Nó do documento ou atributo (índice do elemento e do atributo).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum XNode {
+    Node(usize),
+    Attribute(usize, usize),
+}
+
+impl XNode {
+    // Chave da ordem do documento: os atributos vêm logo depois do próprio
+    // elemento e antes dos filhos
+    fn order_key(self) -> (usize, usize) {
+        match self {
+            XNode::Node(n) => (n, 0),
+            XNode::Attribute(e, i) => (e, i + 1),
+        }
+    }
+}
+
+enum XValue {
+    Nodes(Vec<XNode>),
+    Number(f64),
+    Str(String),
+    Bool(bool),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Axis {
+    Child,
+    Descendant,
+    DescendantOrSelf,
+    Parent,
+    Ancestor,
+    AncestorOrSelf,
+    FollowingSibling,
+    PrecedingSibling,
+    Following,
+    Preceding,
+    Attribute,
+    SelfNode,
+}
+
+enum NodeTest {
+    Name(String),
+    /// `prefixo:*`
+    Prefix(String),
+    /// `*`: qualquer nó do tipo principal do eixo (elemento ou atributo).
+    Any,
+    Node,
+    Text,
+    Comment,
+}
+
+struct Step {
+    axis: Axis,
+    test: NodeTest,
+    predicates: Vec<Expr>,
+}
+
+enum PathStart {
+    Root,
+    Context,
+    Expr(Box<Expr>),
+}
+
+enum Expr {
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Negate(Box<Expr>),
+    Union(Box<Expr>, Box<Expr>),
+    Literal(String),
+    Number(f64),
+    Function(String, Vec<Expr>),
+    /// Expressão primária com predicados: `(//livro)[1]`.
+    Filter(Box<Expr>, Vec<Expr>),
+    Path(PathStart, Vec<Step>),
+}
+
+#[derive(Clone, PartialEq)]
+enum Token {
+    Slash,
+    DoubleSlash,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Dot,
+    DotDot,
+    At,
+    Comma,
+    Pipe,
+    ColonColon,
+    /// `*` como teste de nome; como multiplicação vira `Operator(Mul)`.
+    Star,
+    Operator(BinaryOp),
+    Name(String),
+    Literal(String),
+    Number(f64),
+}
+
+/// Avalia a expressão sobre o XML e devolve os nós encontrados, na ordem do
+/// documento. Expressões que resultam em número, texto ou booleano devolvem um
+/// único item sem caminho.
+pub fn select(xml_str: &str, expression: &str) -> Result<Vec<XPathMatch>, ServiceError> {
+    let expr = parse(expression)?;
+    let dom = xml_converter::xml_to_dom(xml_str)?;
+    let evaluator = Evaluator { dom: &dom };
+    let context = Context {
+        node: XNode::Node(0),
+        position: 1,
+        size: 1,
+    };
+    let matches = match evaluator.eval(&expr, &context)? {
+        XValue::Nodes(nodes) => {
+            let steps = evaluator.path_steps();
+            nodes
+                .into_iter()
+                .map(|n| {
+                    Ok(XPathMatch {
+                        path: evaluator.path_of(n, &steps),
+                        value: evaluator.display_value(n)?,
+                    })
+                })
+                .collect::<Result<_, ServiceError>>()?
+        }
+        other => vec![XPathMatch {
+            path: String::new(),
+            value: evaluator.to_string(&other),
+        }],
+    };
+    Ok(matches)
+}
+
+/// Lista os itens como texto: o caminho de cada nó seguido do seu valor,
+/// separados por uma linha em branco.
+pub fn format_matches(matches: &[XPathMatch]) -> String {
+    matches
+        .iter()
+        .map(|m| {
+            if m.path.is_empty() {
+                m.value.clone()
+            } else {
+                format!("{}\n{}", m.path, m.value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn invalid(message: impl std::fmt::Display) -> ServiceError {
+    ServiceError::validation(format!("invalid XPath: {}", message))
+}
+
+// ---------------------------------------------------------------------------
+// Análise léxica e sintática
+// ---------------------------------------------------------------------------
+
+fn tokenize(expression: &str) -> Result<Vec<(Token, usize)>, ServiceError> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens: Vec<(Token, usize)> = Vec::new();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        let start = pos;
+        let next = chars.get(pos + 1).copied();
+        // Pela especificação, `*` e os nomes `and`, `or`, `div` e `mod` são
+        // operadores quando seguem algo que pode terminar uma expressão
+        let after_operand = tokens.last().is_some_and(|(t, _)| {
+            !matches!(
+                t,
+                Token::At
+                    | Token::ColonColon
+                    | Token::LParen
+                    | Token::LBracket
+                    | Token::Comma
+                    | Token::Operator(_)
+                    | Token::Slash
+                    | Token::DoubleSlash
+                    | Token::Pipe
+            )
+        });
+
+        let token = match c {
+            c if c.is_whitespace() => {
+                pos += 1;
+                continue;
+            }
+            '/' if next == Some('/') => {
+                pos += 2;
+                Token::DoubleSlash
+            }
+            '/' => {
+                pos += 1;
+                Token::Slash
+            }
+            '(' | ')' | '[' | ']' | '@' | ',' | '|' | '=' | '+' | '-' => {
+                pos += 1;
+                match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    '@' => Token::At,
+                    ',' => Token::Comma,
+                    '|' => Token::Pipe,
+                    '=' => Token::Operator(BinaryOp::Eq),
+                    '+' => Token::Operator(BinaryOp::Add),
+                    _ => Token::Operator(BinaryOp::Sub),
+                }
+            }
+            '!' if next == Some('=') => {
+                pos += 2;
+                Token::Operator(BinaryOp::Ne)
+            }
+            '<' | '>' => {
+                let or_equal = next == Some('=');
+                pos += if or_equal { 2 } else { 1 };
+                Token::Operator(match (c, or_equal) {
+                    ('<', false) => BinaryOp::Lt,
+                    ('<', true) => BinaryOp::Le,
+                    ('>', false) => BinaryOp::Gt,
+                    _ => BinaryOp::Ge,
+                })
+            }
+            ':' if next == Some(':') => {
+                pos += 2;
+                Token::ColonColon
+            }
+            '*' => {
+                pos += 1;
+                if after_operand {
+                    Token::Operator(BinaryOp::Mul)
+                } else {
+                    Token::Star
+                }
+            }
+            '.' if next == Some('.') => {
+                pos += 2;
+                Token::DotDot
+            }
+            '.' if !next.is_some_and(|n| n.is_ascii_digit()) => {
+                pos += 1;
+                Token::Dot
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                    pos += 1;
+                }
+                let text: String = chars[start..pos].iter().collect();
+                let number = text.parse::<f64>().map_err(|_| {
+                    invalid(format!("invalid number '{}' at position {}", text, start))
+                })?;
+                Token::Number(number)
+            }
+            '"' | '\'' => {
+                let end = chars[pos + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| invalid(format!("unterminated string at position {}", start)))?;
+                pos += end + 2;
+                Token::Literal(chars[start + 1..pos - 1].iter().collect())
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while pos < chars.len() {
+                    let ch = chars[pos];
+                    let after = chars.get(pos + 1).copied();
+                    if ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.') {
+                        pos += 1;
+                    } else if ch == ':'
+                        && after.is_some_and(|a| a.is_alphabetic() || a == '_' || a == '*')
+                    {
+                        // QName `prefixo:nome` ou teste `prefixo:*`
+                        pos += 2;
+                        if after == Some('*') {
+                            break;
+                        }
+                    } else {
+                        break;
+                    }
+                }
+                let name: String = chars[start..pos].iter().collect();
+                match name.as_str() {
+                    "and" if after_operand => Token::Operator(BinaryOp::And),
+                    "or" if after_operand => Token::Operator(BinaryOp::Or),
+                    "div" if after_operand => Token::Operator(BinaryOp::Div),
+                    "mod" if after_operand => Token::Operator(BinaryOp::Mod),
+                    _ => Token::Name(name),
+                }
+            }
+            '$' => {
+                return Err(invalid(format!(
+                    "variables are not supported (position {})",
+                    start
+                )));
+            }
+            c => {
+                return Err(invalid(format!(
+                    "unexpected character '{}' at position {}",
+                    c, start
+                )));
+            }
+        };
+        tokens.push((token, start));
+    }
+    Ok(tokens)
+}
+
+fn parse(expression: &str) -> Result<Expr, ServiceError> {
+    let tokens = tokenize(expression)?;
+    if tokens.is_empty() {
+        return Err(invalid("empty expression"));
+    }
+    let mut parser = XPathParser {
+        tokens,
+        pos: 0,
+        end: expression.chars().count(),
+    };
+    let expr = parser.parse_or()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parser.error("unexpected token"));
+    }
+    Ok(expr)
+}
+
+struct XPathParser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Tamanho da expressão, usado nas mensagens de erro no fim do texto.
+    end: usize,
+}
+
+impl XPathParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|(t, _)| t)
+    }
+
+    fn error(&self, message: &str) -> ServiceError {
+        let position = self.tokens.get(self.pos).map_or(self.end, |(_, p)| *p);
+        invalid(format!("{} at position {}", message, position))
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token, description: &str) -> Result<(), ServiceError> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", description)))
+        }
+    }
+
+    // Operadores binários de um mesmo nível de precedência, associativos à esquerda
+    fn parse_binary(
+        &mut self,
+        ops: &[BinaryOp],
+        next: fn(&mut Self) -> Result<Expr, ServiceError>,
+    ) -> Result<Expr, ServiceError> {
+        let mut left = next(self)?;
+        while let Some(Token::Operator(op)) = self.peek() {
+            let op = *op;
+            if !ops.contains(&op) {
+                break;
+            }
+            self.pos += 1;
+            let right = next(self)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ServiceError> {
+        self.parse_binary(&[BinaryOp::Or], Self::parse_and)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ServiceError> {
+        self.parse_binary(&[BinaryOp::And], Self::parse_equality)
+    }
+
+    fn parse_equality(&mut self) -> Result<Expr, ServiceError> {
+        self.parse_binary(&[BinaryOp::Eq, BinaryOp::Ne], Self::parse_relational)
+    }
+
+    fn parse_relational(&mut self) -> Result<Expr, ServiceError> {
+        self.parse_binary(
+            &[BinaryOp::Lt, BinaryOp::Le, BinaryOp::Gt, BinaryOp::Ge],
+            Self::parse_additive,
+        )
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, ServiceError> {
+        self.parse_binary(&[BinaryOp::Add, BinaryOp::Sub], Self::parse_multiplicative)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, ServiceError> {
+        self.parse_binary(
+            &[BinaryOp::Mul, BinaryOp::Div, BinaryOp::Mod],
+            Self::parse_unary,
+        )
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ServiceError> {
+        if self.eat(&Token::Operator(BinaryOp::Sub)) {
+            return Ok(Expr::Negate(Box::new(self.parse_unary()?)));
+        }
+        let mut left = self.parse_path()?;
+        while self.eat(&Token::Pipe) {
+            let right = self.parse_path()?;
+            left = Expr::Union(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn starts_step(&self) -> bool {
+        match self.peek() {
+            Some(Token::Star | Token::At | Token::Dot | Token::DotDot) => true,
+            // Nome seguido de `(` é chamada de função, exceto os testes de tipo de nó
+            Some(Token::Name(name)) => {
+                self.peek_at(1) != Some(&Token::LParen) || is_node_type(name)
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<Expr, ServiceError> {
+        if self.eat(&Token::Slash) {
+            let steps = if self.starts_step() {
+                self.parse_relative(Vec::new())?
+            } else {
+                Vec::new()
+            };
+            return Ok(Expr::Path(PathStart::Root, steps));
+        }
+        if self.eat(&Token::DoubleSlash) {
+            let steps = self.parse_relative(vec![descendant_or_self()])?;
+            return Ok(Expr::Path(PathStart::Root, steps));
+        }
+        if self.starts_step() {
+            let steps = self.parse_relative(Vec::new())?;
+            return Ok(Expr::Path(PathStart::Context, steps));
+        }
+
+        let primary = self.parse_primary()?;
+        let predicates = self.parse_predicates()?;
+        let filter = if predicates.is_empty() {
+            primary
+        } else {
+            Expr::Filter(Box::new(primary), predicates)
+        };
+        match self.peek() {
+            Some(Token::Slash) => {
+                self.pos += 1;
+                let steps = self.parse_relative(Vec::new())?;
+                Ok(Expr::Path(PathStart::Expr(Box::new(filter)), steps))
+            }
+            Some(Token::DoubleSlash) => {
+                self.pos += 1;
+                let steps = self.parse_relative(vec![descendant_or_self()])?;
+                Ok(Expr::Path(PathStart::Expr(Box::new(filter)), steps))
+            }
+            _ => Ok(filter),
+        }
+    }
+
+    fn parse_relative(&mut self, mut steps: Vec<Step>) -> Result<Vec<Step>, ServiceError> {
+        steps.push(self.parse_step()?);
+        loop {
+            if self.eat(&Token::Slash) {
+                steps.push(self.parse_step()?);
+            } else if self.eat(&Token::DoubleSlash) {
+                steps.push(descendant_or_self());
+                steps.push(self.parse_step()?);
+            } else {
+                return Ok(steps);
+            }
+        }
+    }
+
+    fn parse_step(&mut self) -> Result<Step, ServiceError> {
+        if self.eat(&Token::Dot) {
+            return Ok(Step {
+                axis: Axis::SelfNode,
+                test: NodeTest::Node,
+                predicates: Vec::new(),
+            });
+        }
+        if self.eat(&Token::DotDot) {
+            return Ok(Step {
+                axis: Axis::Parent,
+                test: NodeTest::Node,
+                predicates: Vec::new(),
+            });
+        }
+
+        let axis = if self.eat(&Token::At) {
+            Axis::Attribute
+        } else if let Some(Token::Name(name)) = self.peek()
+            && self.peek_at(1) == Some(&Token::ColonColon)
+        {
+            let axis = axis_from_name(name).ok_or_else(|| self.error("unknown axis"))?;
+            self.pos += 2;
+            axis
+        } else {
+            Axis::Child
+        };
+
+        let test = match self.peek().cloned() {
+            Some(Token::Star) => {
+                self.pos += 1;
+                NodeTest::Any
+            }
+            Some(Token::Name(name)) if self.peek_at(1) == Some(&Token::LParen) => {
+                let test = match name.as_str() {
+                    "node" => NodeTest::Node,
+                    "text" => NodeTest::Text,
+                    "comment" => NodeTest::Comment,
+                    _ => return Err(self.error("unsupported node test")),
+                };
+                self.pos += 2;
+                self.expect(Token::RParen, "')'")?;
+                test
+            }
+            Some(Token::Name(name)) => {
+                self.pos += 1;
+                match name.strip_suffix(":*") {
+                    Some(prefix) => NodeTest::Prefix(prefix.to_string()),
+                    None => NodeTest::Name(name),
+                }
+            }
+            _ => return Err(self.error("expected a node test")),
+        };
+
+        Ok(Step {
+            axis,
+            test,
+            predicates: self.parse_predicates()?,
+        })
+    }
+
+    fn parse_predicates(&mut self) -> Result<Vec<Expr>, ServiceError> {
+        let mut predicates = Vec::new();
+        while self.eat(&Token::LBracket) {
+            predicates.push(self.parse_or()?);
+            self.expect(Token::RBracket, "']'")?;
+        }
+        Ok(predicates)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ServiceError> {
+        match self.peek().cloned() {
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let expr = self.parse_or()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(expr)
+            }
+            Some(Token::Literal(s)) => {
+                self.pos += 1;
+                Ok(Expr::Literal(s))
+            }
+            Some(Token::Number(n)) => {
+                self.pos += 1;
+                Ok(Expr::Number(n))
+            }
+            Some(Token::Name(name)) => {
+                let Some(&(_, min, max)) = FUNCTIONS.iter().find(|(f, _, _)| *f == name) else {
+                    return Err(self.error(&format!("unknown function '{}'", name)));
+                };
+                self.pos += 2;
+                let mut args = Vec::new();
+                if !self.eat(&Token::RParen) {
+                    loop {
+                        args.push(self.parse_or()?);
+                        if self.eat(&Token::RParen) {
+                            break;
+                        }
+                        self.expect(Token::Comma, "',' or ')'")?;
+                    }
+                }
+                if args.len() < min || args.len() > max {
+                    return Err(invalid(format!("wrong number of arguments for {}()", name)));
+                }
+                Ok(Expr::Function(name, args))
+            }
+            Some(_) => Err(self.error("unexpected token")),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+}
+
+fn descendant_or_self() -> Step {
+    Step {
+        axis: Axis::DescendantOrSelf,
+        test: NodeTest::Node,
+        predicates: Vec::new(),
+    }
+}
+
+fn is_node_type(name: &str) -> bool {
+    matches!(name, "node" | "text" | "comment" | "processing-instruction")
+}
+
+fn axis_from_name(name: &str) -> Option<Axis> {
+    Some(match name {
+        "child" => Axis::Child,
+        "descendant" => Axis::Descendant,
+        "descendant-or-self" => Axis::DescendantOrSelf,
+        "parent" => Axis::Parent,
+        "ancestor" => Axis::Ancestor,
+        "ancestor-or-self" => Axis::AncestorOrSelf,
+        "following-sibling" => Axis::FollowingSibling,
+        "preceding-sibling" => Axis::PrecedingSibling,
+        "following" => Axis::Following,
+        "preceding" => Axis::Preceding,
+        "attribute" => Axis::Attribute,
+        "self" => Axis::SelfNode,
+        _ => return None,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Avaliação
+// ---------------------------------------------------------------------------
+
+struct Context {
+    node: XNode,
+    position: usize,
+    size: usize,
+}
+
+struct Evaluator<'a> {
+    dom: &'a XmlDom,
+}
+
+impl Evaluator<'_> {
+    fn eval(&self, expr: &Expr, ctx: &Context) -> Result<XValue, ServiceError> {
+        match expr {
+            Expr::Binary(BinaryOp::Or, left, right) => Ok(XValue::Bool(
+                self.eval_bool(left, ctx)? || self.eval_bool(right, ctx)?,
+            )),
+            Expr::Binary(BinaryOp::And, left, right) => Ok(XValue::Bool(
+                self.eval_bool(left, ctx)? && self.eval_bool(right, ctx)?,
+            )),
+            Expr::Binary(
+                op @ (BinaryOp::Eq
+                | BinaryOp::Ne
+                | BinaryOp::Lt
+                | BinaryOp::Le
+                | BinaryOp::Gt
+                | BinaryOp::Ge),
+                left,
+                right,
+            ) => {
+                let left = self.eval(left, ctx)?;
+                let right = self.eval(right, ctx)?;
+                Ok(XValue::Bool(self.compare(&left, &right, *op)))
+            }
+            Expr::Binary(op, left, right) => {
+                let a = self.eval_number(left, ctx)?;
+                let b = self.eval_number(right, ctx)?;
+                Ok(XValue::Number(match op {
+                    BinaryOp::Add => a + b,
+                    BinaryOp::Sub => a - b,
+                    BinaryOp::Mul => a * b,
+                    BinaryOp::Div => a / b,
+                    _ => a % b,
+                }))
+            }
+            Expr::Negate(inner) => Ok(XValue::Number(-self.eval_number(inner, ctx)?)),
+            Expr::Union(left, right) => {
+                let mut nodes = self.eval_nodes(left, ctx)?;
+                nodes.extend(self.eval_nodes(right, ctx)?);
+                Ok(XValue::Nodes(document_order(nodes)))
+            }
+            Expr::Literal(s) => Ok(XValue::Str(s.clone())),
+            Expr::Number(n) => Ok(XValue::Number(*n)),
+            Expr::Function(name, args) => self.call(name, args, ctx),
+            Expr::Filter(primary, predicates) => {
+                let nodes = self.eval_nodes(primary, ctx)?;
+                Ok(XValue::Nodes(self.filter(nodes, predicates)?))
+            }
+            Expr::Path(start, steps) => {
+                let mut nodes = match start {
+                    PathStart::Root => vec![XNode::Node(0)],
+                    PathStart::Context => vec![ctx.node],
+                    PathStart::Expr(expr) => self.eval_nodes(expr, ctx)?,
+                };
+                for step in steps {
+                    let mut selected = Vec::new();
+                    for node in nodes {
+                        let candidates: Vec<XNode> = self
+                            .axis_nodes(node, step.axis)
+                            .into_iter()
+                            .filter(|n| self.matches_test(*n, &step.test, step.axis))
+                            .collect();
+                        selected.extend(self.filter(candidates, &step.predicates)?);
+                    }
+                    nodes = document_order(selected);
+                }
+                Ok(XValue::Nodes(nodes))
+            }
+        }
+    }
+
+    fn eval_bool(&self, expr: &Expr, ctx: &Context) -> Result<bool, ServiceError> {
+        self.eval(expr, ctx).map(|v| to_bool(&v))
+    }
+
+    fn eval_number(&self, expr: &Expr, ctx: &Context) -> Result<f64, ServiceError> {
+        self.eval(expr, ctx).map(|v| self.to_number(&v))
+    }
+
+    fn eval_string(&self, expr: &Expr, ctx: &Context) -> Result<String, ServiceError> {
+        self.eval(expr, ctx).map(|v| self.to_string(&v))
+    }
+
+    fn eval_nodes(&self, expr: &Expr, ctx: &Context) -> Result<Vec<XNode>, ServiceError> {
+        match self.eval(expr, ctx)? {
+            XValue::Nodes(nodes) => Ok(nodes),
+            _ => Err(invalid("expression does not return a node-set")),
+        }
+    }
+
+    // Aplica os predicados em sequência; a posição de cada nó segue a ordem do eixo
+    fn filter(
+        &self,
+        mut nodes: Vec<XNode>,
+        predicates: &[Expr],
+    ) -> Result<Vec<XNode>, ServiceError> {
+        for predicate in predicates {
+            let size = nodes.len();
+            let mut kept = Vec::new();
+            for (i, node) in nodes.into_iter().enumerate() {
+                let ctx = Context {
+                    node,
+                    position: i + 1,
+                    size,
+                };
+                let keep = match self.eval(predicate, &ctx)? {
+                    XValue::Number(n) => n == ctx.position as f64,
+                    other => to_bool(&other),
+                };
+                if keep {
+                    kept.push(node);
+                }
+            }
+            nodes = kept;
+        }
+        Ok(nodes)
+    }
+
+    // Nós do eixo, na ordem do eixo (eixos reversos começam pelo nó mais próximo)
+    fn axis_nodes(&self, node: XNode, axis: Axis) -> Vec<XNode> {
+        let nodes = &self.dom.nodes;
+        let (id, is_attribute) = match node {
+            XNode::Node(n) => (n, false),
+            XNode::Attribute(e, _) => (e, true),
+        };
+        let as_nodes = |ids: Vec<usize>| ids.into_iter().map(XNode::Node).collect();
+        match axis {
+            Axis::SelfNode => vec![node],
+            Axis::Parent if is_attribute => vec![XNode::Node(id)],
+            Axis::Parent => nodes[id].parent.map(XNode::Node).into_iter().collect(),
+            Axis::Ancestor | Axis::AncestorOrSelf => {
+                let mut result = Vec::new();
+                if axis == Axis::AncestorOrSelf {
+                    result.push(node);
+                }
+                let mut current = if is_attribute {
+                    Some(id)
+                } else {
+                    nodes[id].parent
+                };
+                while let Some(n) = current {
+                    result.push(XNode::Node(n));
+                    current = nodes[n].parent;
+                }
+                result
+            }
+            Axis::Child
+            | Axis::Descendant
+            | Axis::DescendantOrSelf
+            | Axis::FollowingSibling
+            | Axis::PrecedingSibling
+            | Axis::Attribute
+                if is_attribute =>
+            {
+                if axis == Axis::DescendantOrSelf {
+                    vec![node]
+                } else {
+                    Vec::new()
+                }
+            }
+            Axis::Child => as_nodes(nodes[id].children.clone()),
+            Axis::Descendant => as_nodes(self.descendants(id)),
+            Axis::DescendantOrSelf => {
+                let mut result = vec![id];
+                result.extend(self.descendants(id));
+                as_nodes(result)
+            }
+            Axis::FollowingSibling | Axis::PrecedingSibling => {
+                let Some(parent) = nodes[id].parent else {
+                    return Vec::new();
+                };
+                let siblings = &nodes[parent].children;
+                let index = siblings.iter().position(|&s| s == id).unwrap_or(0);
+                if axis == Axis::FollowingSibling {
+                    as_nodes(siblings[index + 1..].to_vec())
+                } else {
+                    as_nodes(siblings[..index].iter().rev().copied().collect())
+                }
+            }
+            // Os índices seguem a ordem do documento, então os nós seguintes são os
+            // de índice maior que o fim da subárvore, e os anteriores os de índice
+            // menor que não são ancestrais
+            Axis::Following => {
+                let end = if is_attribute {
+                    id
+                } else {
+                    self.subtree_end(id)
+                };
+                as_nodes((end + 1..nodes.len()).collect())
+            }
+            Axis::Preceding => {
+                let mut ancestors = Vec::new();
+                let mut current = Some(id);
+                while let Some(n) = current {
+                    ancestors.push(n);
+                    current = nodes[n].parent;
+                }
+                let last = if is_attribute { id + 1 } else { id };
+                as_nodes((0..last).rev().filter(|n| !ancestors.contains(n)).collect())
+            }
+            // Declarações de namespace não são atributos no XPath
+            Axis::Attribute => match &nodes[id].kind {
+                DomNodeKind::Element { attributes, .. } => (0..attributes.len())
+                    .filter(|&i| {
+                        let name = &attributes[i].0;
+                        name != "xmlns" && !name.starts_with("xmlns:")
+                    })
+                    .map(|i| XNode::Attribute(id, i))
+                    .collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    fn descendants(&self, id: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut pending: Vec<usize> = self.dom.nodes[id].children.iter().rev().copied().collect();
+        while let Some(n) = pending.pop() {
+            result.push(n);
+            pending.extend(self.dom.nodes[n].children.iter().rev());
+        }
+        result
+    }
+
+    fn subtree_end(&self, mut id: usize) -> usize {
+        while let Some(&last) = self.dom.nodes[id].children.last() {
+            id = last;
+        }
+        id
+    }
+
+    fn matches_test(&self, node: XNode, test: &NodeTest, axis: Axis) -> bool {
+        match node {
+            XNode::Attribute(e, i) => {
+                // Atributos só passam por testes de nome no eixo de atributos
+                let name = &self.attribute(e, i).0;
+                match test {
+                    NodeTest::Node => true,
+                    NodeTest::Any => axis == Axis::Attribute,
+                    NodeTest::Name(n) => axis == Axis::Attribute && name == n,
+                    NodeTest::Prefix(p) => axis == Axis::Attribute && has_prefix(name, p),
+                    NodeTest::Text | NodeTest::Comment => false,
+                }
+            }
+            XNode::Node(n) => match (&self.dom.nodes[n].kind, test) {
+                (_, NodeTest::Node) => true,
+                (DomNodeKind::Text(_), NodeTest::Text) => true,
+                (DomNodeKind::Comment(_), NodeTest::Comment) => true,
+                (DomNodeKind::Element { .. }, NodeTest::Any) => axis != Axis::Attribute,
+                (DomNodeKind::Element { name, .. }, NodeTest::Name(test_name)) => {
+                    axis != Axis::Attribute && name == test_name
+                }
+                (DomNodeKind::Element { name, .. }, NodeTest::Prefix(prefix)) => {
+                    axis != Axis::Attribute && has_prefix(name, prefix)
+                }
+                _ => false,
+            },
+        }
+    }
+
+    fn attribute(&self, element: usize, index: usize) -> &(String, String) {
+        match &self.dom.nodes[element].kind {
+            DomNodeKind::Element { attributes, .. } => &attributes[index],
+            _ => unreachable!("atributo fora de um elemento"),
+        }
+    }
+
+    fn node_name(&self, node: XNode) -> String {
+        match node {
+            XNode::Attribute(e, i) => self.attribute(e, i).0.clone(),
+            XNode::Node(n) => match &self.dom.nodes[n].kind {
+                DomNodeKind::Element { name, .. } => name.clone(),
+                _ => String::new(),
+            },
+        }
+    }
+
+    fn string_value(&self, node: XNode) -> String {
+        match node {
+            XNode::Attribute(e, i) => self.attribute(e, i).1.clone(),
+            XNode::Node(n) => match &self.dom.nodes[n].kind {
+                DomNodeKind::Text(t) | DomNodeKind::Comment(t) => t.clone(),
+                DomNodeKind::Document | DomNodeKind::Element { .. } => self
+                    .descendants(n)
+                    .into_iter()
+                    .filter_map(|d| match &self.dom.nodes[d].kind {
+                        DomNodeKind::Text(t) => Some(t.as_str()),
+                        _ => None,
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    fn to_string(&self, value: &XValue) -> String {
+        match value {
+            XValue::Nodes(nodes) => nodes
+                .first()
+                .map(|n| self.string_value(*n))
+                .unwrap_or_default(),
+            XValue::Number(n) => number_to_string(*n),
+            XValue::Str(s) => s.clone(),
+            XValue::Bool(b) => b.to_string(),
+        }
+    }
+
+    fn to_number(&self, value: &XValue) -> f64 {
+        match value {
+            XValue::Number(n) => *n,
+            XValue::Bool(b) => f64::from(u8::from(*b)),
+            other => string_to_number(&self.to_string(other)),
+        }
+    }
+
+    // Comparação da especificação: conjuntos de nós são comparados nó a nó,
+    // e basta um par satisfazer o operador
+    fn compare(&self, left: &XValue, right: &XValue, op: BinaryOp) -> bool {
+        match (left, right) {
+            (XValue::Nodes(_), XValue::Bool(_)) | (XValue::Bool(_), XValue::Nodes(_)) => {
+                compare_atoms(
+                    &XValue::Bool(to_bool(left)),
+                    &XValue::Bool(to_bool(right)),
+                    op,
+                )
+            }
+            (XValue::Nodes(nodes), other) => nodes
+                .iter()
+                .any(|n| self.compare(&XValue::Str(self.string_value(*n)), other, op)),
+            (other, XValue::Nodes(nodes)) => nodes
+                .iter()
+                .any(|n| self.compare(other, &XValue::Str(self.string_value(*n)), op)),
+            _ => compare_atoms(left, right, op),
+        }
+    }
+
+    fn call(&self, name: &str, args: &[Expr], ctx: &Context) -> Result<XValue, ServiceError> {
+        // Argumento opcional que, quando ausente, é o nó de contexto
+        let node_arg = |i: usize| -> Result<Option<XNode>, ServiceError> {
+            match args.get(i) {
+                Some(arg) => Ok(self.eval_nodes(arg, ctx)?.first().copied()),
+                None => Ok(Some(ctx.node)),
+            }
+        };
+        let string_arg = |i: usize| -> Result<String, ServiceError> {
+            match args.get(i) {
+                Some(arg) => self.eval_string(arg, ctx),
+                None => Ok(self.string_value(ctx.node)),
+            }
+        };
+
+        Ok(match name {
+            "last" => XValue::Number(ctx.size as f64),
+            "position" => XValue::Number(ctx.position as f64),
+            "count" => XValue::Number(self.eval_nodes(&args[0], ctx)?.len() as f64),
+            "name" => XValue::Str(node_arg(0)?.map(|n| self.node_name(n)).unwrap_or_default()),
+            "local-name" => XValue::Str(
+                node_arg(0)?
+                    .map(|n| local_name(&self.node_name(n)).to_string())
+                    .unwrap_or_default(),
+            ),
+            "namespace-uri" => XValue::Str(String::new()),
+            "string" => XValue::Str(string_arg(0)?),
+            "concat" => XValue::Str(
+                args.iter()
+                    .map(|a| self.eval_string(a, ctx))
+                    .collect::<Result<String, _>>()?,
+            ),
+            "starts-with" => XValue::Bool(string_arg(0)?.starts_with(&string_arg(1)?)),
+            "contains" => XValue::Bool(string_arg(0)?.contains(&string_arg(1)?)),
+            "substring-before" => {
+                let (s, sep) = (string_arg(0)?, string_arg(1)?);
+                XValue::Str(
+                    s.split_once(&sep)
+                        .map(|(b, _)| b.to_string())
+                        .unwrap_or_default(),
+                )
+            }
+            "substring-after" => {
+                let (s, sep) = (string_arg(0)?, string_arg(1)?);
+                XValue::Str(
+                    s.split_once(&sep)
+                        .map(|(_, a)| a.to_string())
+                        .unwrap_or_default(),
+                )
+            }
+            "substring" => {
+                // Posições começam em 1 e são arredondadas, como na especificação
+                let s = string_arg(0)?;
+                let start = xpath_round(self.eval_number(&args[1], ctx)?);
+                let end = match args.get(2) {
+                    Some(len) => start + xpath_round(self.eval_number(len, ctx)?),
+                    None => f64::INFINITY,
+                };
+                XValue::Str(
+                    s.chars()
+                        .enumerate()
+                        .filter(|(i, _)| {
+                            let p = (*i + 1) as f64;
+                            p >= start && p < end
+                        })
+                        .map(|(_, c)| c)
+                        .collect(),
+                )
+            }
+            "string-length" => XValue::Number(string_arg(0)?.chars().count() as f64),
+            "normalize-space" => XValue::Str(
+                string_arg(0)?
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            "translate" => {
+                let (s, from, to) = (string_arg(0)?, string_arg(1)?, string_arg(2)?);
+                let from: Vec<char> = from.chars().collect();
+                let to: Vec<char> = to.chars().collect();
+                XValue::Str(
+                    s.chars()
+                        .filter_map(|c| match from.iter().position(|&f| f == c) {
+                            Some(i) => to.get(i).copied(),
+                            None => Some(c),
+                        })
+                        .collect(),
+                )
+            }
+            "boolean" => XValue::Bool(self.eval_bool(&args[0], ctx)?),
+            "not" => XValue::Bool(!self.eval_bool(&args[0], ctx)?),
+            "true" => XValue::Bool(true),
+            "false" => XValue::Bool(false),
+            "lang" => {
+                let lang = string_arg(0)?.to_lowercase();
+                let declared = self
+                    .axis_nodes(ctx.node, Axis::AncestorOrSelf)
+                    .into_iter()
+                    .find_map(|n| match n {
+                        XNode::Node(id) => match &self.dom.nodes[id].kind {
+                            DomNodeKind::Element { attributes, .. } => attributes
+                                .iter()
+                                .find(|(k, _)| k == "xml:lang")
+                                .map(|(_, v)| v.to_lowercase()),
+                            _ => None,
+                        },
+                        XNode::Attribute(..) => None,
+                    });
+                XValue::Bool(declared.is_some_and(|d| {
+                    d == lang || d.strip_prefix(&lang).is_some_and(|r| r.starts_with('-'))
+                }))
+            }
+            "number" => XValue::Number(match args.first() {
+                Some(arg) => self.eval_number(arg, ctx)?,
+                None => string_to_number(&self.string_value(ctx.node)),
+            }),
+            "sum" => XValue::Number(
+                self.eval_nodes(&args[0], ctx)?
+                    .into_iter()
+                    .map(|n| string_to_number(&self.string_value(n)))
+                    .sum(),
+            ),
+            "floor" => XValue::Number(self.eval_number(&args[0], ctx)?.floor()),
+            "ceiling" => XValue::Number(self.eval_number(&args[0], ctx)?.ceil()),
+            "round" => XValue::Number(xpath_round(self.eval_number(&args[0], ctx)?)),
+            "id" => {
+                // Sem DTD, usa os atributos `id` e `xml:id`
+                let ids: Vec<String> = match self.eval(&args[0], ctx)? {
+                    XValue::Nodes(nodes) => nodes
+                        .into_iter()
+                        .flat_map(|n| {
+                            self.string_value(n)
+                                .split_whitespace()
+                                .map(str::to_string)
+                                .collect::<Vec<_>>()
+                        })
+                        .collect(),
+                    other => self
+                        .to_string(&other)
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect(),
+                };
+                let found = (0..self.dom.nodes.len())
+                    .filter(|&n| match &self.dom.nodes[n].kind {
+                        DomNodeKind::Element { attributes, .. } => attributes
+                            .iter()
+                            .any(|(k, v)| (k == "id" || k == "xml:id") && ids.contains(v)),
+                        _ => false,
+                    })
+                    .map(XNode::Node)
+                    .collect();
+                XValue::Nodes(found)
+            }
+            _ => return Err(invalid(format!("unknown function '{}'", name))),
+        })
+    }
+
+    // Caminho absoluto a partir dos passos de `path_steps`
+    fn path_of(&self, node: XNode, steps: &[String]) -> String {
+        let id = match node {
+            XNode::Attribute(e, i) => {
+                return format!(
+                    "{}/@{}",
+                    self.path_of(XNode::Node(e), steps),
+                    self.attribute(e, i).0
+                );
+            }
+            XNode::Node(0) => return "/".to_string(),
+            XNode::Node(n) => n,
+        };
+        let mut ancestors = vec![id];
+        let mut current = id;
+        while let Some(parent) = self.dom.nodes[current].parent.filter(|&p| p != 0) {
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+            .iter()
+            .rev()
+            .map(|&n| format!("/{}", steps[n]))
+            .collect()
+    }
+
+    // Passo de cada nó no caminho, com índice entre irmãos de mesmo nome quando
+    // há mais de um; calculado em uma passada para não varrer os irmãos a cada nó
+    fn path_steps(&self) -> Vec<String> {
+        let nodes = &self.dom.nodes;
+        let mut steps = vec![String::new(); nodes.len()];
+        for node in nodes {
+            let names: Vec<String> = node
+                .children
+                .iter()
+                .map(|&c| match &nodes[c].kind {
+                    DomNodeKind::Element { name, .. } => name.clone(),
+                    DomNodeKind::Text(_) => "text()".to_string(),
+                    DomNodeKind::Comment(_) => "comment()".to_string(),
+                    DomNodeKind::Document => String::new(),
+                })
+                .collect();
+            let mut totals: HashMap<&str, usize> = HashMap::new();
+            for name in &names {
+                *totals.entry(name.as_str()).or_default() += 1;
+            }
+            let mut seen: HashMap<&str, usize> = HashMap::new();
+            for (&child, name) in node.children.iter().zip(&names) {
+                let index = seen.entry(name.as_str()).or_default();
+                *index += 1;
+                steps[child] = if totals[name.as_str()] > 1 {
+                    format!("{}[{}]", name, index)
+                } else {
+                    name.clone()
+                };
+            }
+        }
+        steps
+    }
+
+    fn display_value(&self, node: XNode) -> Result<String, ServiceError> {
+        match node {
+            XNode::Node(n) if matches!(self.dom.nodes[n].kind, DomNodeKind::Element { .. }) => {
+                let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 4);
+                self.write_node(&mut writer, n)?;
+                String::from_utf8(writer.into_inner().into_inner()).map_err(ServiceError::encoding)
+            }
+            XNode::Node(0) => {
+                // Documento inteiro: todos os nós de nível superior
+                let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 4);
+                for &child in &self.dom.nodes[0].children {
+                    self.write_node(&mut writer, child)?;
+                }
+                String::from_utf8(writer.into_inner().into_inner()).map_err(ServiceError::encoding)
+            }
+            other => Ok(self.string_value(other)),
+        }
+    }
+
+    fn write_node<W: std::io::Write>(
+        &self,
+        writer: &mut Writer<W>,
+        id: usize,
+    ) -> Result<(), ServiceError> {
+        let node = &self.dom.nodes[id];
+        let event = match &node.kind {
+            DomNodeKind::Element { name, attributes } => {
+                let mut elem = BytesStart::new(name.as_str());
+                for (key, value) in attributes {
+                    elem.push_attribute((key.as_str(), value.as_str()));
+                }
+                if node.children.is_empty() {
+                    Event::Empty(elem)
+                } else {
+                    writer
+                        .write_event(Event::Start(elem))
+                        .map_err(|e| ServiceError::serialize("XML", e))?;
+                    for &child in &node.children {
+                        self.write_node(writer, child)?;
+                    }
+                    Event::End(BytesEnd::new(name.as_str()))
+                }
+            }
+            DomNodeKind::Text(t) => Event::Text(BytesText::new(t)),
+            DomNodeKind::Comment(c) => Event::Comment(BytesText::from_escaped(c.as_str())),
+            DomNodeKind::Document => return Ok(()),
+        };
+        writer
+            .write_event(event)
+            .map_err(|e| ServiceError::serialize("XML", e))
+    }
+}
+
+fn document_order(mut nodes: Vec<XNode>) -> Vec<XNode> {
+    nodes.sort_by_key(|n| n.order_key());
+    nodes.dedup();
+    nodes
+}
+
+fn to_bool(value: &XValue) -> bool {
+    match value {
+        XValue::Nodes(nodes) => !nodes.is_empty(),
+        XValue::Number(n) => *n != 0.0 && !n.is_nan(),
+        XValue::Str(s) => !s.is_empty(),
+        XValue::Bool(b) => *b,
+    }
+}
+
+// Comparação entre valores simples: `=` e `!=` usam booleano ou número se um dos
+// lados for desse tipo e texto nos demais casos; `<`, `>` sempre comparam números
+fn compare_atoms(left: &XValue, right: &XValue, op: BinaryOp) -> bool {
+    let number = |v: &XValue| match v {
+        XValue::Number(n) => *n,
+        XValue::Bool(b) => f64::from(u8::from(*b)),
+        XValue::Str(s) => string_to_number(s),
+        XValue::Nodes(_) => f64::NAN,
+    };
+    let equal = match (left, right) {
+        (XValue::Bool(_), _) | (_, XValue::Bool(_)) => to_bool(left) == to_bool(right),
+        (XValue::Number(_), _) | (_, XValue::Number(_)) => number(left) == number(right),
+        (XValue::Str(a), XValue::Str(b)) => a == b,
+        _ => false,
+    };
+    match op {
+        BinaryOp::Eq => equal,
+        BinaryOp::Ne => !equal,
+        BinaryOp::Lt => number(left) < number(right),
+        BinaryOp::Le => number(left) <= number(right),
+        BinaryOp::Gt => number(left) > number(right),
+        BinaryOp::Ge => number(left) >= number(right),
+        _ => false,
+    }
+}
+
+// Só aceita a sintaxe numérica do XPath (sem expoente, `inf` ou `nan`)
+fn string_to_number(s: &str) -> f64 {
+    let s = s.trim();
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return f64::NAN;
+    }
+    s.parse().unwrap_or(f64::NAN)
+}
+
+fn number_to_string(n: f64) -> String {
+    if n.is_nan() {
+        "NaN".to_string()
+    } else if n.is_infinite() {
+        if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else if n == 0.0 {
+        "0".to_string()
+    } else {
+        n.to_string()
+    }
+}
+
+// O XPath arredonda .5 para cima, inclusive nos negativos
+fn xpath_round(n: f64) -> f64 {
+    if n.is_finite() { (n + 0.5).floor() } else { n }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+fn has_prefix(name: &str, prefix: &str) -> bool {
+    name.split_once(':').is_some_and(|(p, _)| p == prefix)
+}
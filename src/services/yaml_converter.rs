@@ -1,61 +1,476 @@
-use serde::Serialize;
-use serde_json::Value;
-use yaml_rust2::YamlLoader;
+use std::collections::{HashMap, HashSet};
 
-use crate::services::json_converter;
+use serde_json::{Map, Value};
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser, Tag};
+use yaml_rust2::scanner::{Marker, ScanError, TScalarStyle};
+use yaml_rust2::{Yaml, YamlEmitter, YamlLoader};
 
-pub fn yaml_to_json(yaml_str: &str) -> Result<String, String> {
-    let docs = YamlLoader::load_from_str(yaml_str)
-        .map_err(|e| format!("Erro ao fazer parse do YAML: {}", e))?;
+use crate::enums::format_converter::FormatConverter;
+use crate::services::error::{ServiceError, SourceLocation};
+use crate::services::format::{self, Format};
+use crate::services::{
+    csv_converter, json_converter, ndjson_converter, toml_converter, xml_converter,
+};
+use crate::structs::converter_options::ConverterOptions;
+use crate::structs::csv_options::CsvOptions;
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+
+pub struct YamlFormat;
+
+impl Format for YamlFormat {
+    fn kind(&self) -> FormatConverter {
+        FormatConverter::Yaml
+    }
+
+    fn name(&self) -> &'static str {
+        "YAML"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["yaml", "yml"]
+    }
+
+    fn parse(&self, input: &str, options: &ConverterOptions) -> Result<Value, ServiceError> {
+        yaml_to_value(input, &options.yaml)
+    }
+
+    fn parse_documents(
+        &self,
+        input: &str,
+        options: &ConverterOptions,
+    ) -> Result<Vec<Value>, ServiceError> {
+        yaml_to_documents(input, &options.yaml)
+    }
+
+    fn emit(&self, value: &Value, options: &ConverterOptions) -> Result<String, ServiceError> {
+        value_to_yaml(value, &options.yaml)
+    }
+}
+
+/// Fluxos com vários documentos (`---`) viram um array com um item por documento.
+pub fn yaml_to_value(yaml_str: &str, options: &YamlOptions) -> Result<Value, ServiceError> {
+    yaml_to_documents(yaml_str, options).map(format::merge_documents)
+}
+
+pub fn yaml_to_documents(
+    yaml_str: &str,
+    options: &YamlOptions,
+) -> Result<Vec<Value>, ServiceError> {
+    let mut loader = ValueLoader::new(options);
+    let result = Parser::new_from_str(yaml_str).load(&mut loader, true);
+    if loader.alias_nodes > MAX_ALIAS_NODES {
+        return Err(ServiceError::parse("YAML", "too many alias expansions"));
+    }
+    // Um `---` no fim abre um documento sem conteúdo, que não deve virar `null`
+    if loader.docs.len() > 1 && loader.last_doc_empty {
+        loader.docs.pop();
+    }
+    let docs = result
+        .and_then(|_| loader.error.map_or(Ok(loader.docs), Err))
+        .map_err(|e| {
+            let marker = e.marker();
+            let location =
+                SourceLocation::from_line_char_column(yaml_str, marker.line(), marker.col());
+            ServiceError::syntax("YAML", e.info(), e.clone(), location)
+        })?;
     if docs.is_empty() {
-        return Err("YAML vazio".to_string());
-    }
-    let doc = &docs[0];
-    // Converte Yaml para Value
-    let value = yaml_to_json_value(doc);
-    let mut buf = Vec::new();
-    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
-    let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
-    value
-        .serialize(&mut ser)
-        .map_err(|e| format!("Erro ao serializar JSON: {}", e))?;
-    String::from_utf8(buf).map_err(|e| format!("Erro ao converter JSON para UTF-8: {}", e))
-}
-
-fn yaml_to_json_value(yaml: &yaml_rust2::Yaml) -> Value {
-    use yaml_rust2::Yaml;
+        return Err(ServiceError::parse("YAML", "document is empty"));
+    }
+    Ok(docs)
+}
+
+// Prefixo das tags `!!` do esquema padrão do YAML
+const CORE_TAG_HANDLE: &str = "tag:yaml.org,2002:";
+
+// Limite de nós copiados por aliases no fluxo inteiro; âncoras que repetem a
+// anterior várias vezes ("billion laughs") crescem exponencialmente
+const MAX_ALIAS_NODES: usize = 100_000;
+
+enum LoaderNode {
+    Sequence {
+        anchor: usize,
+        tag: Option<Tag>,
+        items: Vec<Value>,
+    },
+    Mapping {
+        anchor: usize,
+        tag: Option<Tag>,
+        entries: Vec<MappingEntry>,
+        keys: HashSet<String>,
+        pending_key: Option<MappingKey>,
+    },
+}
+
+enum MappingKey {
+    Name(String),
+    Merge,
+}
+
+enum MappingEntry {
+    Pair(String, Value),
+    Merge(Vec<Map<String, Value>>),
+}
+
+/// Monta `Value` direto dos eventos do parser, resolvendo âncoras, aliases e
+/// merge keys (`<<`), que o `YamlLoader` não trata.
+struct ValueLoader<'a> {
+    options: &'a YamlOptions,
+    docs: Vec<Value>,
+    root: Option<Value>,
+    stack: Vec<LoaderNode>,
+    /// Valor de cada âncora e quantos nós ele tem.
+    anchors: HashMap<usize, (Value, usize)>,
+    /// Nós já inseridos por aliases, limitado por `MAX_ALIAS_NODES`.
+    alias_nodes: usize,
+    /// O último documento não tinha nenhum nó (nem mesmo `null` explícito).
+    last_doc_empty: bool,
+    error: Option<ScanError>,
+}
+
+impl<'a> ValueLoader<'a> {
+    fn new(options: &'a YamlOptions) -> Self {
+        ValueLoader {
+            options,
+            docs: Vec::new(),
+            root: None,
+            stack: Vec::new(),
+            anchors: HashMap::new(),
+            alias_nodes: 0,
+            last_doc_empty: false,
+            error: None,
+        }
+    }
+
+    fn handle_event(&mut self, ev: Event, mark: Marker) -> Result<(), ScanError> {
+        match ev {
+            Event::DocumentStart => {
+                self.anchors.clear();
+                self.last_doc_empty = false;
+            }
+            Event::DocumentEnd => {
+                // Documento vazio vira null
+                if self.root.is_none() {
+                    self.last_doc_empty = true;
+                }
+                let doc = self.root.take().unwrap_or(Value::Null);
+                self.docs.push(doc);
+            }
+            Event::SequenceStart(anchor, tag) => self.stack.push(LoaderNode::Sequence {
+                anchor,
+                tag,
+                items: Vec::new(),
+            }),
+            Event::MappingStart(anchor, tag) => self.stack.push(LoaderNode::Mapping {
+                anchor,
+                tag,
+                entries: Vec::new(),
+                keys: HashSet::new(),
+                pending_key: None,
+            }),
+            Event::SequenceEnd | Event::MappingEnd => match self.stack.pop() {
+                Some(LoaderNode::Sequence { anchor, tag, items }) => {
+                    let value = self.tagged(Value::Array(items), tag);
+                    self.insert_node(value, anchor, false, mark)?;
+                }
+                Some(LoaderNode::Mapping {
+                    anchor,
+                    tag,
+                    entries,
+                    keys,
+                    ..
+                }) => {
+                    let value = self.tagged(Value::Object(build_mapping(entries, &keys)), tag);
+                    self.insert_node(value, anchor, false, mark)?;
+                }
+                None => {}
+            },
+            Event::Scalar(text, style, anchor, tag) => {
+                // O parser representa um documento vazio como escalar vazio na raiz
+                if self.stack.is_empty()
+                    && text.is_empty()
+                    && style == TScalarStyle::Plain
+                    && anchor == 0
+                    && tag.is_none()
+                {
+                    self.last_doc_empty = true;
+                }
+                let merge_key = style == TScalarStyle::Plain
+                    && text == "<<"
+                    && tag.as_ref().is_none_or(|t| is_core_tag(t, "merge"));
+                let value = scalar_to_value(text, style, tag.as_ref())
+                    .map_err(|message| ScanError::new_string(mark, message))?;
+                let value = self.tagged(value, tag);
+                self.insert_node(value, anchor, merge_key, mark)?;
+            }
+            Event::Alias(id) => {
+                let (value, nodes) = self
+                    .anchors
+                    .get(&id)
+                    .ok_or_else(|| ScanError::new(mark, "alias refers to an unknown anchor"))?;
+                // Conta antes de copiar, para não alocar a expansão inteira
+                self.alias_nodes = self.alias_nodes.saturating_add(*nodes);
+                if self.alias_nodes > MAX_ALIAS_NODES {
+                    return Err(ScanError::new(mark, "too many alias expansions"));
+                }
+                let value = value.clone();
+                self.insert_node(value, 0, false, mark)?;
+            }
+            Event::Nothing | Event::StreamStart | Event::StreamEnd => {}
+        }
+        Ok(())
+    }
+
+    fn insert_node(
+        &mut self,
+        value: Value,
+        anchor: usize,
+        merge_key: bool,
+        mark: Marker,
+    ) -> Result<(), ScanError> {
+        // Âncoras válidas começam em 1
+        if anchor > 0 {
+            self.anchors
+                .insert(anchor, (value.clone(), count_nodes(&value)));
+        }
+        match self.stack.last_mut() {
+            None => self.root = Some(value),
+            Some(LoaderNode::Sequence { items, .. }) => items.push(value),
+            Some(LoaderNode::Mapping {
+                entries,
+                keys,
+                pending_key,
+                ..
+            }) => match pending_key.take() {
+                None if merge_key => *pending_key = Some(MappingKey::Merge),
+                None => *pending_key = Some(MappingKey::Name(key_to_string(value))),
+                Some(MappingKey::Merge) => entries.push(MappingEntry::Merge(
+                    merge_sources(value).map_err(|message| ScanError::new_string(mark, message))?,
+                )),
+                Some(MappingKey::Name(key)) => {
+                    if !keys.insert(key.clone()) {
+                        return Err(ScanError::new_string(
+                            mark,
+                            format!("duplicated key '{}' in mapping", key),
+                        ));
+                    }
+                    entries.push(MappingEntry::Pair(key, value));
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn tagged(&self, value: Value, tag: Option<Tag>) -> Value {
+        match tag {
+            Some(tag) if self.options.keep_tags && tag.handle != CORE_TAG_HANDLE => {
+                let mut map = Map::new();
+                map.insert(
+                    self.options.tag_key.clone(),
+                    Value::String(format!("{}{}", tag.handle, tag.suffix)),
+                );
+                map.insert(self.options.value_key.clone(), value);
+                Value::Object(map)
+            }
+            _ => value,
+        }
+    }
+}
+
+impl MarkedEventReceiver for ValueLoader<'_> {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = self.handle_event(ev, mark) {
+            self.error = Some(e);
+        }
+    }
+}
+
+fn count_nodes(value: &Value) -> usize {
+    match value {
+        Value::Array(items) => 1 + items.iter().map(count_nodes).sum::<usize>(),
+        Value::Object(map) => 1 + map.values().map(count_nodes).sum::<usize>(),
+        _ => 1,
+    }
+}
+
+fn is_core_tag(tag: &Tag, suffix: &str) -> bool {
+    tag.handle == CORE_TAG_HANDLE && tag.suffix == suffix
+}
+
+// Chaves explícitas sempre vencem; entre mapas mesclados, o primeiro vence
+fn build_mapping(entries: Vec<MappingEntry>, keys: &HashSet<String>) -> Map<String, Value> {
+    let mut map = Map::new();
+    for entry in entries {
+        match entry {
+            MappingEntry::Pair(key, value) => {
+                map.insert(key, value);
+            }
+            MappingEntry::Merge(sources) => {
+                for source in sources {
+                    for (key, value) in source {
+                        if !keys.contains(&key) && !map.contains_key(&key) {
+                            map.insert(key, value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    map
+}
+
+fn merge_sources(value: Value) -> Result<Vec<Map<String, Value>>, String> {
+    let invalid = || "merge key '<<' expects a mapping or a list of mappings".to_string();
+    match value {
+        Value::Object(map) => Ok(vec![map]),
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Value::Object(map) => Ok(map),
+                _ => Err(invalid()),
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+// Escalares como `1` ou `true` viram o próprio texto; chaves compostas viram JSON
+fn key_to_string(key: Value) -> String {
+    match key {
+        Value::String(s) => s,
+        Value::Array(_) | Value::Object(_) => serde_json::to_string(&key).unwrap_or_default(),
+        other => other.to_string(),
+    }
+}
+
+fn scalar_to_value(text: String, style: TScalarStyle, tag: Option<&Tag>) -> Result<Value, String> {
+    if style != TScalarStyle::Plain {
+        return Ok(Value::String(text));
+    }
+    let tag = match tag {
+        None => return Ok(yaml_scalar_to_value(Yaml::from_str(&text))),
+        Some(tag) if tag.handle == CORE_TAG_HANDLE => tag,
+        // Tags personalizadas não mudam o tipo: o escalar fica como texto
+        Some(_) => return Ok(Value::String(text)),
+    };
+    let resolved = Yaml::from_str(&text);
+    let value = match (tag.suffix.as_str(), resolved) {
+        ("int", Yaml::Integer(i)) => Some(Value::from(i)),
+        ("float", Yaml::Integer(i)) => Some(Value::from(i as f64)),
+        ("float", real @ Yaml::Real(_)) => Some(yaml_scalar_to_value(real)),
+        ("bool", Yaml::Boolean(b)) => Some(Value::Bool(b)),
+        ("null", Yaml::Null) => Some(Value::Null),
+        ("int" | "float" | "bool" | "null", _) => None,
+        _ => Some(Value::String(text.clone())),
+    };
+    value.ok_or_else(|| format!("invalid !!{} value '{}'", tag.suffix, text))
+}
+
+fn yaml_scalar_to_value(yaml: Yaml) -> Value {
     match yaml {
-        Yaml::Null => Value::Null,
-        Yaml::Boolean(b) => Value::Bool(*b),
-        Yaml::Integer(i) => Value::Number((*i).into()),
-        Yaml::Real(s) => serde_json::Number::from_f64(s.parse::<f64>().unwrap_or(0.0))
+        Yaml::Boolean(b) => Value::Bool(b),
+        Yaml::Integer(i) => Value::Number(i.into()),
+        // .inf e .nan não existem em JSON e ficam como texto
+        Yaml::Real(s) => s
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
             .map(Value::Number)
-            .unwrap_or(Value::Null),
-        Yaml::String(s) => Value::String(s.clone()),
-        Yaml::Array(arr) => Value::Array(arr.iter().map(yaml_to_json_value).collect()),
-        Yaml::Hash(h) => {
-            let mut map = serde_json::Map::new();
-            for (k, v) in h {
-                let key = match k {
-                    Yaml::String(s) => s.clone(),
-                    _ => format!("{:?}", k),
-                };
-                map.insert(key, yaml_to_json_value(v));
-            }
-            Value::Object(map)
-        }
+            .unwrap_or(Value::String(s)),
+        Yaml::String(s) => Value::String(s),
         _ => Value::Null,
     }
 }
 
-pub fn yaml_to_csv(yaml_str: &str) -> Result<String, String> {
-    yaml_to_json(yaml_str).and_then(|json_str| json_converter::json_to_csv(&json_str))
+pub fn value_to_yaml(value: &Value, options: &YamlOptions) -> Result<String, ServiceError> {
+    match value {
+        Value::Array(docs) if options.multi_document => {
+            let mut out = Vec::with_capacity(docs.len());
+            for doc in docs {
+                out.push(emit_yaml_document(doc)?);
+            }
+            Ok(out.join("\n"))
+        }
+        _ => emit_yaml_document(value),
+    }
 }
 
-pub fn yaml_to_xml(yaml_str: &str) -> Result<String, String> {
-    yaml_to_json(yaml_str).and_then(|json_str| json_converter::json_to_xml(&json_str))
+fn emit_yaml_document(value: &Value) -> Result<String, ServiceError> {
+    // JSON é um subconjunto de YAML, então o loader aceita a serialização compacta
+    let yaml_str = serde_json::to_string(value).map_err(|e| ServiceError::serialize("JSON", e))?;
+    let docs =
+        YamlLoader::load_from_str(&yaml_str).map_err(|e| ServiceError::serialize("YAML", e))?;
+    let mut out_str = String::new();
+    let mut emitter = YamlEmitter::new(&mut out_str);
+    for doc in docs {
+        emitter
+            .dump(&doc)
+            .map_err(|e| ServiceError::serialize("YAML", e))?;
+    }
+    Ok(out_str)
 }
 
-pub fn pretty_yaml(yaml_str: &str) -> Result<String, String> {
-    yaml_to_json(yaml_str).and_then(|json_str| json_converter::json_to_yaml(&json_str))
+pub fn yaml_to_json(yaml_str: &str) -> Result<String, ServiceError> {
+    yaml_to_value(yaml_str, &YamlOptions::default()).and_then(|v| json_converter::value_to_json(&v))
+}
+
+pub fn yaml_to_csv(yaml_str: &str) -> Result<String, ServiceError> {
+    yaml_to_value(yaml_str, &YamlOptions::default())
+        .and_then(|v| csv_converter::value_to_csv(&v, &CsvOptions::default()))
+}
+
+pub fn yaml_to_xml(yaml_str: &str) -> Result<String, ServiceError> {
+    yaml_to_value(yaml_str, &YamlOptions::default())
+        .and_then(|v| xml_converter::value_to_xml(&v, &XmlOptions::default()))
+}
+
+pub fn pretty_yaml(yaml_str: &str) -> Result<String, ServiceError> {
+    yaml_to_value(yaml_str, &YamlOptions::default())
+        .and_then(|v| value_to_yaml(&v, &YamlOptions::default()))
+}
+
+pub fn yaml_to_toml(yaml_str: &str) -> Result<String, ServiceError> {
+    yaml_to_value(yaml_str, &YamlOptions::default()).and_then(|v| toml_converter::value_to_toml(&v))
+}
+
+pub fn yaml_to_ndjson(yaml_str: &str) -> Result<String, ServiceError> {
+    yaml_to_value(yaml_str, &YamlOptions::default())
+        .and_then(|v| ndjson_converter::value_to_ndjson(&v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_exponential_alias_expansion() {
+        let mut yaml = String::from("a0: &a0 [x, x, x, x, x, x, x, x, x, x]\n");
+        for i in 1..10 {
+            let aliases = vec![format!("*a{}", i - 1); 10].join(", ");
+            yaml.push_str(&format!("a{i}: &a{i} [{aliases}]\n"));
+        }
+        let error = yaml_to_value(&yaml, &YamlOptions::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            ServiceError::parse("YAML", "too many alias expansions").to_string()
+        );
+    }
+
+    #[test]
+    fn trailing_separator_adds_no_document() {
+        let docs = yaml_to_documents("a: 1\n---\nb: 2\n---\n", &YamlOptions::default()).unwrap();
+        assert_eq!(docs.len(), 2);
+        let docs = yaml_to_documents("a: 1\n--- null\n", &YamlOptions::default()).unwrap();
+        assert_eq!(docs.len(), 2);
+    }
+
+    #[test]
+    fn expands_small_aliases() {
+        let yaml = "base: &base {a: 1}\nother: *base\n";
+        let value = yaml_to_value(yaml, &YamlOptions::default()).unwrap();
+        assert_eq!(value["other"]["a"], 1);
+    }
 }
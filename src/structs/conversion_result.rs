@@ -0,0 +1,12 @@
+use crate::structs::format_detection::FormatDetection;
+
+pub struct ConversionResult {
+    pub output: String,
+    /// Quantidade de documentos lidos da entrada (ex.: fluxo YAML com `---`).
+    pub documents: usize,
+    /// Formato identificado quando a entrada é `Auto`.
+    pub detected: Option<FormatDetection>,
+    /// Nós encontrados pela consulta XPath; nesse caso a saída é a lista deles.
+    /// Fica vazio quando a expressão resulta em número, texto ou booleano.
+    pub matches: Option<usize>,
+}
@@ -0,0 +1,16 @@
+use crate::enums::query_language::QueryLanguage;
+use crate::structs::csv_options::CsvOptions;
+use crate::structs::xml_options::XmlOptions;
+use crate::structs::yaml_options::YamlOptions;
+
+/// Opções de leitura e escrita repassadas a todos os formatos do conversor.
+#[derive(Clone, Default)]
+pub struct ConverterOptions {
+    pub csv: CsvOptions,
+    pub xml: XmlOptions,
+    pub yaml: YamlOptions,
+    /// Expressão JSONPath aplicada à entrada antes de gerar a saída, ou XPath
+    /// que lista os nós da entrada XML, conforme `query_language`.
+    pub query: Option<String>,
+    pub query_language: QueryLanguage,
+}
@@ -0,0 +1,42 @@
+use crate::enums::csv_column_type::CsvColumnType;
+
+#[derive(Clone)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// Caractere de escape das aspas; `None` usa aspas duplicadas (`""`).
+    pub escape: Option<u8>,
+    /// Sem cabeçalho, as colunas de entrada se chamam `col1..colN` e a saída não tem
+    /// a linha de títulos.
+    pub has_headers: bool,
+    /// Remove espaços em volta dos campos na leitura.
+    pub trim: bool,
+    /// Linhas que começam com este caractere são ignoradas na leitura.
+    pub comment: Option<u8>,
+    /// Escreve o BOM UTF-8 na saída, para o Excel reconhecer a codificação.
+    pub bom: bool,
+    /// Expande objetos e arrays em colunas como `address.city` e `tags[0]`, e
+    /// reconstrói a estrutura a partir desses cabeçalhos na leitura.
+    pub flatten: bool,
+    /// Converte colunas em que todas as linhas têm o mesmo tipo; células vazias viram null.
+    pub infer_types: bool,
+    /// Tipos forçados por nome de coluna, usados mesmo sem `infer_types`.
+    pub column_types: Vec<(String, CsvColumnType)>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            has_headers: true,
+            trim: false,
+            comment: None,
+            bom: false,
+            flatten: false,
+            infer_types: false,
+            column_types: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde_json::Value;
+
+use crate::enums::diff_kind::DiffKind;
+
+pub struct DiffEntry {
+    pub kind: DiffKind,
+    /// Caminho no formato JSONPath (ex.: `$.itens[2].preco`).
+    pub path: String,
+    /// Valor no primeiro documento; vazio quando o caminho foi adicionado.
+    pub left: Option<Value>,
+    /// Valor no segundo documento; vazio quando o caminho foi removido.
+    pub right: Option<Value>,
+}
@@ -0,0 +1,6 @@
+#[derive(Clone, Default)]
+pub struct DiffOptions {
+    /// Compara arrays como multiconjuntos: a mesma lista em outra ordem não
+    /// gera diferenças.
+    pub ignore_array_order: bool,
+}
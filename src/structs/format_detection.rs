@@ -0,0 +1,10 @@
+use crate::enums::format_converter::FormatConverter;
+
+#[derive(Clone, Copy, Debug)]
+pub struct FormatDetection {
+    pub format: FormatConverter,
+    /// Confiança entre 0.0 e 1.0.
+    pub confidence: f32,
+    /// Delimitador provável quando o formato é CSV.
+    pub csv_delimiter: Option<u8>,
+}
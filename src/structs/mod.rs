@@ -1,2 +1,13 @@
+pub mod conversion_result;
+pub mod converter_options;
+pub mod csv_options;
+pub mod diff_entry;
+pub mod diff_options;
+pub mod format_detection;
 pub mod request_data;
 pub mod request_result;
+pub mod schema_violation;
+pub mod xml_dom;
+pub mod xml_options;
+pub mod xpath_match;
+pub mod yaml_options;
@@ -0,0 +1,8 @@
+pub struct SchemaViolation {
+    /// JSON Pointer do valor inválido no documento (vazio na raiz).
+    pub instance_path: String,
+    /// JSON Pointer da palavra-chave no schema (ex.: `/properties/idade/minimum`).
+    pub schema_path: String,
+    pub keyword: String,
+    pub message: String,
+}
@@ -0,0 +1,22 @@
+/// Árvore de um documento XML, na ordem do documento. O nó 0 é a raiz do
+/// documento, pai do elemento raiz.
+pub struct XmlDom {
+    pub nodes: Vec<DomNode>,
+}
+
+pub struct DomNode {
+    pub kind: DomNodeKind,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+}
+
+pub enum DomNodeKind {
+    Document,
+    Element {
+        /// Nome qualificado, com o prefixo de namespace (ex.: `soap:Body`).
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    Text(String),
+    Comment(String),
+}
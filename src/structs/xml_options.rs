@@ -0,0 +1,27 @@
+use crate::enums::xml_content_mode::XmlContentMode;
+
+#[derive(Clone)]
+pub struct XmlOptions {
+    /// Prefixo das chaves JSON que representam atributos (ex.: `@id`).
+    pub attribute_prefix: String,
+    /// Chave do texto de elementos que também têm atributos ou filhos.
+    pub text_key: String,
+    /// Chave do array ordenado de filhos (textos e elementos) no modo sem perdas.
+    pub children_key: String,
+    pub content_mode: XmlContentMode,
+    /// Mantém o elemento raiz como chave do objeto, permitindo recriá-lo na volta para XML.
+    /// Desligado, o nome da raiz se perde e a escrita usa `<root>`.
+    pub keep_root: bool,
+}
+
+impl Default for XmlOptions {
+    fn default() -> Self {
+        XmlOptions {
+            attribute_prefix: "@".to_string(),
+            text_key: "_text".to_string(),
+            children_key: "$children".to_string(),
+            content_mode: XmlContentMode::Compact,
+            keep_root: false,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+/// Item encontrado por uma consulta XPath.
+pub struct XPathMatch {
+    /// Caminho absoluto do nó (ex.: `/catalogo/livro[2]/@id`); vazio quando a
+    /// expressão devolve um número, texto ou booleano.
+    pub path: String,
+    /// XML do elemento, valor do atributo ou conteúdo do texto ou comentário.
+    pub value: String,
+}
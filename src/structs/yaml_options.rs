@@ -0,0 +1,20 @@
+#[derive(Clone)]
+pub struct YamlOptions {
+    /// Gera um fluxo de documentos separados por `---` quando a raiz é um array.
+    pub multi_document: bool,
+    /// Mantém tags personalizadas (ex.: `!Ref`) como `{tag_key: "!Ref", value_key: valor}`.
+    pub keep_tags: bool,
+    pub tag_key: String,
+    pub value_key: String,
+}
+
+impl Default for YamlOptions {
+    fn default() -> Self {
+        YamlOptions {
+            multi_document: false,
+            keep_tags: false,
+            tag_key: "$tag".to_string(),
+            value_key: "$value".to_string(),
+        }
+    }
+}